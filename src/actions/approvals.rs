@@ -9,6 +9,8 @@ use alloy::network::EthereumWallet;
 use alloy::providers::ProviderBuilder;
 use alloy::signers::local::LocalSigner;
 use alloy::signers::Signer as _;
+use std::future::IntoFuture;
+use crate::metrics::{self, Service};
 
 const USDC_ADDRESS: &str = "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174";
 
@@ -83,7 +85,7 @@ pub async fn check_allowance<P: Provider + Clone>(
     spender: Address,
 ) -> Result<U256> {
     let contract = IERC20::new(token_address, provider.clone());
-    let allowance = contract.allowance(owner, spender).call().await?;
+    let allowance = metrics::timed(Service::Rpc, "allowance", contract.allowance(owner, spender).call().into_future()).await?;
     Ok(allowance)
 }
 
@@ -96,7 +98,7 @@ pub async fn create_approve_tx<P: Provider + Clone>(
     let contract = IERC20::new(token_address, provider.clone());
 
     let tx = contract.approve(spender, amount);
-    let pending_tx = tx.send().await?;
+    let pending_tx = metrics::timed(Service::Rpc, "approve", tx.send()).await?;
     let receipt = pending_tx.get_receipt().await?;
 
     Ok(format!("{:?}", receipt.transaction_hash))
@@ -112,7 +114,7 @@ pub async fn approval_process(
         .connect_http("https://polygon-rpc.com".parse()?);
     let user_address = signer.address();
 
-    let balance = provider.get_balance(user_address).await?;
+    let balance = metrics::timed(Service::Rpc, "eth_getBalance", provider.get_balance(user_address).into_future()).await?;
     if balance < U256::from(MIN_MATIC_BALANCE) {
         return Ok(ApprovalResult {
             success: false,
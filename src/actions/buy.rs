@@ -3,14 +3,17 @@ use alloy::providers::ProviderBuilder;
 use alloy::signers::local::LocalSigner;
 use alloy::signers::Signer as _;
 use std::str::FromStr;
-use eyre::Result;
+use eyre::{ensure, eyre, Result};
 use polymarket_client_sdk::clob::{Client, Config};
 use polymarket_client_sdk::{
-    clob::types::{Amount, Side},
+    clob::types::{Amount, OrderStatusType, Side},
 };
 use polymarket_client_sdk::clob::types::response::PostOrderResponse;
 use rust_decimal::Decimal;
 
+use crate::config;
+use crate::metrics::{self, OrderResult, Service};
+
 const CLOB_URL: &str = "https://clob.polymarket.com";
 
 fn parse_string_list(s: &str) -> Vec<String> {
@@ -20,16 +23,24 @@ fn parse_string_list(s: &str) -> Vec<String> {
         .collect()
 }
 
+/// Risk checks every buy goes through, whether it comes from the TUI or the API.
+pub fn check_amount(amount: f64) -> Result<()> {
+    ensure!(amount.is_finite() && amount > 0.0, "Amount must be a positive number of USDC");
+    let max = config::get().max_order_usdc;
+    ensure!(max <= 0.0 || amount <= max, "Amount is over the {} USDC order limit", max);
+    Ok(())
+}
+
 pub async fn buy_yes(private_key: &str, clob_ids: String, option: &str, amount: String) -> Result<PostOrderResponse> {
+    check_amount(amount.parse()?)?;
     let clob_ids_parsed = parse_string_list(&clob_ids);
 
-
-    let opt: String;
-    if option == "Yes" {
-        opt = clob_ids_parsed.get(0).unwrap().to_string();
-    } else {
-        opt = clob_ids_parsed.get(1).unwrap().to_string();
-    }
+    let index = if option == "Yes" { 0 } else { 1 };
+    let opt = clob_ids_parsed
+        .get(index)
+        .filter(|id| !id.is_empty())
+        .ok_or_else(|| eyre!("Market has no {} token", option))?
+        .to_string();
 
     let signer = LocalSigner::from_str(private_key)?.with_chain_id(Some(137));
     let wallet = EthereumWallet::from(signer.clone());
@@ -39,10 +50,8 @@ pub async fn buy_yes(private_key: &str, clob_ids: String, option: &str, amount:
         .connect_http("https://polygon-rpc.com".parse()?);
     let _user_address = signer.address();
 
-    let client = Client::new(CLOB_URL, Config::default())?
-        .authentication_builder(&signer)
-        .authenticate()
-        .await?;
+    let client = Client::new(CLOB_URL, Config::default())?;
+    let client = metrics::timed(Service::Clob, "auth", client.authentication_builder(&signer).authenticate()).await?;
 
     let amount_dec = Decimal::from_str(&amount)?;
 
@@ -55,7 +64,17 @@ pub async fn buy_yes(private_key: &str, clob_ids: String, option: &str, amount:
         .await?;
 
     let signed_order = client.sign(&signer, market_order).await?;
-    let _posted_order = client.post_order(signed_order).await?;
+    let metrics = metrics::get();
+    metrics.order(OrderResult::Submitted);
+    let posted = metrics::timed(Service::Clob, "order", client.post_order(signed_order)).await;
+    match &posted {
+        Ok(response) if response.success && response.error_msg.as_deref().unwrap_or_default().is_empty() => {
+            if response.status == OrderStatusType::Matched {
+                metrics.order(OrderResult::Filled);
+            }
+        }
+        _ => metrics.order(OrderResult::Rejected),
+    }
 
-    Ok(_posted_order)
+    Ok(posted?)
 }
\ No newline at end of file
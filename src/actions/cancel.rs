@@ -0,0 +1,21 @@
+use alloy::signers::local::LocalSigner;
+use alloy::signers::Signer as _;
+use std::str::FromStr;
+use eyre::Result;
+use polymarket_client_sdk::clob::{Client, Config};
+use polymarket_client_sdk::clob::types::response::CancelOrdersResponse;
+
+use crate::metrics::{self, Service};
+
+const CLOB_URL: &str = "https://clob.polymarket.com";
+
+/// Cancels every open order of the wallet.
+pub async fn cancel_all(private_key: &str) -> Result<CancelOrdersResponse> {
+    let signer = LocalSigner::from_str(private_key)?.with_chain_id(Some(137));
+
+    let client = Client::new(CLOB_URL, Config::default())?;
+    let client = metrics::timed(Service::Clob, "auth", client.authentication_builder(&signer).authenticate()).await?;
+
+    let response = metrics::timed(Service::Clob, "cancel-all", client.cancel_all_orders()).await?;
+    Ok(response)
+}
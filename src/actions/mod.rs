@@ -1,3 +1,5 @@
 pub mod buy;
 pub mod wallet_info;
-pub mod approvals;
\ No newline at end of file
+pub mod approvals;
+pub mod cancel;
+pub mod orders;
\ No newline at end of file
@@ -0,0 +1,67 @@
+use alloy::signers::local::LocalSigner;
+use alloy::signers::Signer as _;
+use std::str::FromStr;
+use eyre::Result;
+use polymarket_client_sdk::clob::{Client, Config};
+use polymarket_client_sdk::clob::types::request::OrdersRequest;
+use serde::Serialize;
+
+use crate::metrics::{self, Service};
+
+const CLOB_URL: &str = "https://clob.polymarket.com";
+/// Cursor the CLOB returns on the last page.
+const LAST_PAGE: &str = "LTE=";
+
+/// An open order of the wallet, flattened for display and export.
+#[derive(Serialize, Clone)]
+pub struct OpenOrder {
+    pub id: String,
+    pub market: String,
+    pub asset_id: String,
+    pub outcome: String,
+    pub side: String,
+    pub price: f64,
+    pub original_size: f64,
+    pub size_matched: f64,
+    pub status: String,
+    /// Unix seconds.
+    pub created_at: i64,
+}
+
+/// Lists every open order of the wallet.
+pub async fn open_orders(private_key: &str) -> Result<Vec<OpenOrder>> {
+    let signer = LocalSigner::from_str(private_key)?.with_chain_id(Some(137));
+
+    let client = Client::new(CLOB_URL, Config::default())?;
+    let client = metrics::timed(Service::Clob, "auth", client.authentication_builder(&signer).authenticate()).await?;
+
+    let mut orders = Vec::new();
+    let mut cursor = None;
+    loop {
+        let request = OrdersRequest::default();
+        let page = metrics::timed(Service::Clob, "orders", client.orders(&request, cursor)).await?;
+        orders.extend(page.data.into_iter().map(|o| OpenOrder {
+            id: o.id,
+            market: o.market,
+            asset_id: o.asset_id,
+            outcome: o.outcome,
+            side: format!("{:?}", o.side),
+            price: o.price.try_into().unwrap_or(0.0),
+            original_size: o.original_size.try_into().unwrap_or(0.0),
+            size_matched: o.size_matched.try_into().unwrap_or(0.0),
+            status: format!("{:?}", o.status),
+            created_at: o.created_at.timestamp(),
+        }));
+        if page.next_cursor.is_empty() || page.next_cursor == LAST_PAGE {
+            break;
+        }
+        cursor = Some(page.next_cursor);
+    }
+    Ok(orders)
+}
+
+/// Address of the wallet behind `private_key`.
+pub fn wallet_address(private_key: &str) -> Result<String> {
+    let signer = LocalSigner::from_str(private_key)?;
+    Ok(signer.address().to_string())
+}
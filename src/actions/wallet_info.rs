@@ -2,13 +2,13 @@ use alloy::{primitives::address, providers::ProviderBuilder, sol};
 use std::error::Error;
 use std::str::FromStr;
 use alloy::network::EthereumWallet;
-use alloy::primitives::{Address, U256};
+use alloy::primitives::Address;
 use alloy::signers::local::LocalSigner;
 use alloy::signers::Signer as _;
-use crate::actions::approvals::{is_fully_approved, ApprovalStatus};
-use alloy::providers::{Provider, RootProvider};
-use alloy::transports::http::Http;
-use reqwest::Client;
+use crate::actions::approvals::is_fully_approved;
+use alloy::providers::Provider;
+use std::future::IntoFuture;
+use crate::metrics::{self, Service};
 
 const USDCE: Address = address!("0x2791bca1f2de4661ed88a30c99a7a9449aa84174");
 
@@ -28,9 +28,9 @@ pub async fn get_wallet_full(private_key: &str) -> Result<(Address, f64, f64, bo
 
     let is_approved = is_fully_approved(&provider, user_address).await?;
 
-    let pol_balance = provider.get_balance(user_address).await?;
+    let pol_balance = metrics::timed(Service::Rpc, "eth_getBalance", provider.get_balance(user_address).into_future()).await?;
     let erc20 = ERC20::new(USDCE, provider.clone());
-    let balance = erc20.balanceOf(user_address).call().await?;
+    let balance = metrics::timed(Service::Rpc, "balanceOf", erc20.balanceOf(user_address).call().into_future()).await?;
     let usdce_balance = balance.to::<u128>() as f64 / 1e6;
     let balance_f64 = pol_balance.to::<u128>() as f64 / 1e18;
     Ok((user_address, usdce_balance, balance_f64, is_approved))
@@ -0,0 +1,254 @@
+use std::env;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+
+use axum::extract::{Path, Query, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use dotenv::dotenv;
+use serde::{Deserialize, Serialize};
+
+use crate::actions::buy::{buy_yes, check_amount};
+use crate::actions::orders::{open_orders, wallet_address, OpenOrder};
+use crate::config;
+use crate::data::get_market::get_market_from_slug;
+use crate::data::pipeline::{market_prices, EventSender};
+use crate::data::positions::{get_positions, Position};
+use crate::data::prices::MarketPrices;
+use crate::data::state::{SharedState, Trade};
+use crate::data::types::MarketSpecificDetails;
+use crate::headless::Report;
+
+/// Environment variable holding the bearer token every request must carry.
+const TOKEN_VAR: &str = "PMTERM_API_TOKEN";
+
+struct Api {
+    state: SharedState,
+    events: EventSender,
+    token: String,
+    private_key: Option<String>,
+}
+
+/// An error response, sent as `{"error": "..."}`.
+struct ApiError(StatusCode, String);
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        #[derive(Serialize)]
+        struct Body {
+            error: String,
+        }
+        (self.0, Json(Body { error: self.1 })).into_response()
+    }
+}
+
+type ApiResult<T> = Result<Json<T>, ApiError>;
+
+/// Binds the API on 127.0.0.1 at `api_port` and serves it in the background. Does
+/// nothing when the port is 0; fails when the token is missing or the port is taken.
+pub async fn start(state: SharedState, events: EventSender) -> anyhow::Result<()> {
+    let port = config::get().api_port;
+    if port == 0 {
+        return Ok(());
+    }
+    dotenv().ok();
+    let token = env::var(TOKEN_VAR).unwrap_or_default();
+    anyhow::ensure!(!token.is_empty(), "api_port is set but {} is not", TOKEN_VAR);
+
+    let api = Arc::new(Api {
+        state,
+        events,
+        token,
+        private_key: env::var("PRIVATE_KEY").ok().filter(|k| !k.is_empty()),
+    });
+    let listener = tokio::net::TcpListener::bind(SocketAddr::from((Ipv4Addr::LOCALHOST, port))).await?;
+    tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, router(api)).await {
+            tracing::error!("API server stopped: {}", e);
+        }
+    });
+    Ok(())
+}
+
+fn router(api: Arc<Api>) -> Router {
+    Router::new()
+        .route("/stats", get(stats))
+        .route("/tape", get(tape))
+        .route("/markets/{slug}", get(market))
+        .route("/positions", get(positions))
+        .route("/orders", get(orders).post(place_order))
+        .layer(middleware::from_fn_with_state(api.clone(), authorize))
+        .with_state(api)
+}
+
+/// Rejects any request without `Authorization: Bearer <token>`.
+async fn authorize(State(api): State<Arc<Api>>, request: Request, next: Next) -> Response {
+    let authorized = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|token| same_token(token, &api.token));
+    if !authorized {
+        return ApiError(StatusCode::UNAUTHORIZED, String::from("Missing or wrong API token")).into_response();
+    }
+    next.run(request).await
+}
+
+/// Compares in time independent of where the tokens first differ.
+fn same_token(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given.bytes().zip(expected.bytes()).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+impl Api {
+    fn private_key(&self) -> Result<&str, ApiError> {
+        self.private_key
+            .as_deref()
+            .ok_or_else(|| ApiError(StatusCode::SERVICE_UNAVAILABLE, String::from("PRIVATE_KEY is not set")))
+    }
+}
+
+/// Slugs go into gamma URLs as a path segment, so only the characters real slugs
+/// use are let through.
+fn check_slug(slug: &str) -> Result<(), ApiError> {
+    let valid = !slug.is_empty() && slug.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-');
+    if !valid {
+        return Err(ApiError(StatusCode::BAD_REQUEST, String::from("slug must be lowercase letters, digits and dashes")));
+    }
+    Ok(())
+}
+
+fn upstream(e: impl std::fmt::Display) -> ApiError {
+    ApiError(StatusCode::BAD_GATEWAY, e.to_string())
+}
+
+#[derive(Deserialize)]
+struct StatsQuery {
+    #[serde(default = "default_top")]
+    top: usize,
+}
+
+fn default_top() -> usize {
+    20
+}
+
+/// Totals and top lists for every statistics window, in the headless output format.
+async fn stats(State(api): State<Arc<Api>>, Query(query): Query<StatsQuery>) -> Response {
+    let snapshot = api.state.borrow().clone();
+    Json(Report::new(&snapshot, query.top)).into_response()
+}
+
+async fn tape(State(api): State<Arc<Api>>) -> Json<Vec<Trade>> {
+    Json(api.state.borrow().tape.clone())
+}
+
+#[derive(Serialize)]
+struct MarketResponse {
+    details: MarketSpecificDetails,
+    /// Session price statistics from the live feed, if the market has traded.
+    live: Option<MarketPrices>,
+}
+
+async fn market(State(api): State<Arc<Api>>, Path(slug): Path<String>) -> ApiResult<MarketResponse> {
+    check_slug(&slug)?;
+    let details = get_market_from_slug(&slug).await.map_err(upstream)?;
+    let live = market_prices(&api.events, &slug).await;
+    Ok(Json(MarketResponse { details, live }))
+}
+
+async fn positions(State(api): State<Arc<Api>>) -> ApiResult<Vec<Position>> {
+    let address = wallet_address(api.private_key()?).map_err(upstream)?;
+    Ok(Json(get_positions(&address).await.map_err(upstream)?))
+}
+
+async fn orders(State(api): State<Arc<Api>>) -> ApiResult<Vec<OpenOrder>> {
+    Ok(Json(open_orders(api.private_key()?).await.map_err(upstream)?))
+}
+
+#[derive(Deserialize)]
+struct OrderRequest {
+    slug: String,
+    /// "Yes" or "No".
+    outcome: String,
+    /// USDC to spend.
+    amount: f64,
+}
+
+#[derive(Serialize)]
+struct OrderResponse {
+    order_id: String,
+    status: String,
+    /// USDC spent.
+    making_amount: String,
+    /// Shares received.
+    taking_amount: String,
+}
+
+/// Places a market buy the same way the buy dialog on a market page does.
+async fn place_order(State(api): State<Arc<Api>>, Json(order): Json<OrderRequest>) -> ApiResult<OrderResponse> {
+    let private_key = api.private_key()?;
+    if order.outcome != "Yes" && order.outcome != "No" {
+        return Err(ApiError(StatusCode::BAD_REQUEST, String::from("outcome must be Yes or No")));
+    }
+    check_amount(order.amount).map_err(|e| ApiError(StatusCode::BAD_REQUEST, e.to_string()))?;
+    check_slug(&order.slug)?;
+
+    let market = get_market_from_slug(&order.slug).await.map_err(upstream)?;
+    if !market.is_tradable() {
+        return Err(ApiError(StatusCode::CONFLICT, String::from("Market is closed to new orders")));
+    }
+    let response = buy_yes(private_key, market.clob_token_ids, &order.outcome, order.amount.to_string())
+        .await
+        .map_err(upstream)?;
+    if let Some(error) = response.error_msg.filter(|e| !e.is_empty()) {
+        return Err(upstream(error));
+    }
+    Ok(Json(OrderResponse {
+        order_id: response.order_id,
+        status: response.status.to_string(),
+        making_amount: response.making_amount.to_string(),
+        taking_amount: response.taking_amount.to_string(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::pipeline;
+
+    #[test]
+    fn compares_tokens() {
+        assert!(same_token("secret", "secret"));
+        assert!(!same_token("secreT", "secret"));
+        assert!(!same_token("secret", "secret2"));
+        assert!(!same_token("", "secret"));
+    }
+
+    #[test]
+    fn accepts_only_slug_characters() {
+        assert!(check_slug("will-it-rain-2026").is_ok());
+        for slug in ["", "Rain", "rain/../orders", "rain?x=1", "rain%2f", "rain it"] {
+            assert!(check_slug(slug).is_err(), "{:?} accepted", slug);
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_missing_or_wrong_tokens() {
+        let (events, state) = pipeline::spawn(None);
+        let api = Arc::new(Api { state, events, token: String::from("secret"), private_key: None });
+        let listener = tokio::net::TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+        let url = format!("http://{}/tape", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router(api)).await });
+
+        let client = reqwest::Client::builder().no_proxy().build().unwrap();
+        let status = |request: reqwest::RequestBuilder| async move { request.send().await.unwrap().status() };
+        assert_eq!(status(client.get(&url)).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(status(client.get(&url).bearer_auth("wrong")).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(status(client.get(&url).header(header::AUTHORIZATION, "secret")).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(status(client.get(&url).bearer_auth("secret")).await, StatusCode::OK);
+    }
+}
@@ -0,0 +1,64 @@
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+
+use crate::data::state::TimeWindow;
+use crate::export::ExportFormat;
+
+/// Live Polymarket trade statistics in the terminal.
+#[derive(Parser, Debug)]
+#[command(version, about)]
+pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Run without the TUI, writing snapshots of the statistics as JSON lines.
+    #[arg(long)]
+    pub headless: bool,
+
+    /// File to append headless snapshots to instead of stdout.
+    #[arg(long, requires = "headless")]
+    pub output: Option<PathBuf>,
+
+    /// Seconds between headless snapshots.
+    #[arg(long, default_value_t = 10, requires = "headless")]
+    pub interval: u64,
+
+    /// Entries per list in each headless snapshot.
+    #[arg(long, default_value_t = 20, requires = "headless")]
+    pub top: usize,
+
+    /// Save every raw feed message to a compressed recording in this directory.
+    #[arg(long, value_name = "DIR", num_args = 0..=1, default_missing_value = "recordings")]
+    pub record: Option<PathBuf>,
+
+    /// Play back a recording instead of connecting to the live feed.
+    #[arg(long, value_name = "FILE", conflicts_with = "record")]
+    pub replay: Option<PathBuf>,
+
+    /// Playback speed for --replay, e.g. 10 for ten times real time, 0 for no delays.
+    #[arg(long, default_value_t = 1.0, requires = "replay")]
+    pub speed: f64,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Export the saved statistics, trade tape, positions and open orders, then exit.
+    Export {
+        /// Directory to write the files to. Defaults to `export_dir` from the config.
+        #[arg(long)]
+        dir: Option<PathBuf>,
+
+        /// File format. Defaults to `export_format` from the config.
+        #[arg(long, value_enum)]
+        format: Option<ExportFormat>,
+
+        /// Statistics window for Top Markets and Top Traders: 5m, 1h, 24h or session.
+        #[arg(long, default_value = "session", value_parser = parse_window)]
+        window: TimeWindow,
+    },
+}
+
+fn parse_window(label: &str) -> Result<TimeWindow, String> {
+    TimeWindow::from_label(label).ok_or_else(|| format!("unknown window '{}'", label))
+}
@@ -0,0 +1,122 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::sync::OnceLock;
+
+const DEFAULT_CONFIG_PATH: &str = "pmterm.toml";
+/// Warnings from dependencies, info and up from pmterm itself.
+pub const DEFAULT_LOG_LEVEL: &str = "warn,pmterm=info";
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct Config {
+    /// Minimum notional (price * size, in USDC) for a trade to show on the tape.
+    pub tape_threshold: f64,
+    /// Most markets kept per statistics window before the least recently traded is dropped.
+    pub max_markets: usize,
+    /// Most traders kept per statistics window before the least recently active is dropped.
+    pub max_traders: usize,
+    /// Most trades kept in the rolling trade log.
+    pub max_log_trades: usize,
+    /// SQLite file every live trade is saved to. Empty disables saving.
+    pub trade_db: String,
+    /// Hours of saved trades loaded back into the statistics on startup.
+    pub rehydrate_hours: u64,
+    /// Saved trades older than this many days are deleted on startup.
+    pub trade_db_retention_days: u64,
+    /// Colour theme: dark, light, high-contrast or colorblind.
+    pub theme: String,
+    /// Directory exports are written to.
+    pub export_dir: String,
+    /// Export file format: csv or json.
+    pub export_format: String,
+    /// Markets ending within this many hours are listed as closing soon.
+    pub closing_horizon_hours: u64,
+    /// Largest single buy in USDC, from the TUI or the API. 0 means no limit.
+    pub max_order_usdc: f64,
+    /// Port of the local HTTP API on 127.0.0.1. 0 disables it.
+    pub api_port: u16,
+    /// Address the Prometheus `/metrics` endpoint listens on. Empty disables it.
+    pub metrics_addr: String,
+    /// Directory of the daily log files. Empty disables file logging.
+    pub log_dir: String,
+    /// Log filter in `RUST_LOG` syntax, e.g. "warn,pmterm=info,pmterm::data::ws=debug".
+    pub log_level: String,
+    /// Daily log files kept before the oldest is deleted.
+    pub log_files: usize,
+    /// Live feed subscriptions as "topic/type", e.g. "activity/trades" or "crypto_prices/update".
+    pub feed_subscriptions: Vec<String>,
+    pub keys: KeysConfig,
+}
+
+/// Key binding overrides: action name to the keys that trigger it. `global` applies
+/// to every page before the page's own section.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct KeysConfig {
+    pub global: HashMap<String, Vec<String>>,
+    pub dashboard: HashMap<String, Vec<String>>,
+    pub detail: HashMap<String, Vec<String>>,
+    pub wallet: HashMap<String, Vec<String>>,
+    pub trader: HashMap<String, Vec<String>>,
+    pub logs: HashMap<String, Vec<String>>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            tape_threshold: 1000.0,
+            max_markets: 5_000,
+            max_traders: 20_000,
+            max_log_trades: 200_000,
+            trade_db: String::from("pmterm.db"),
+            rehydrate_hours: 24,
+            trade_db_retention_days: 30,
+            theme: String::from("dark"),
+            export_dir: String::from("exports"),
+            export_format: String::from("csv"),
+            closing_horizon_hours: 24,
+            max_order_usdc: 0.0,
+            api_port: 0,
+            metrics_addr: String::new(),
+            log_dir: String::from("logs"),
+            log_level: String::from(DEFAULT_LOG_LEVEL),
+            log_files: 7,
+            feed_subscriptions: [
+                "activity/trades",
+                "comments/comment_created",
+                "comments/comment_removed",
+                "comments/reaction_created",
+                "comments/reaction_removed",
+            ]
+            .map(String::from)
+            .to_vec(),
+            keys: KeysConfig::default(),
+        }
+    }
+}
+
+impl Config {
+    /// Reads `pmterm.toml` (or the file named by `PMTERM_CONFIG`), falling back to defaults.
+    pub fn load() -> Self {
+        let path = env::var("PMTERM_CONFIG").unwrap_or_else(|_| DEFAULT_CONFIG_PATH.to_string());
+        let Ok(text) = fs::read_to_string(&path) else {
+            return Self::default();
+        };
+        toml::from_str(&text).unwrap_or_else(|e| {
+            eprintln!("Ignoring invalid config {}: {}", path, e);
+            Self::default()
+        })
+    }
+}
+
+pub fn init() -> &'static Config {
+    CONFIG.get_or_init(Config::load)
+}
+
+pub fn get() -> &'static Config {
+    init()
+}
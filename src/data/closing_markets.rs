@@ -0,0 +1,76 @@
+use std::time::Duration;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Serialize;
+
+use crate::config;
+use crate::data::get_market::MarketError;
+use crate::data::market_meta::{get_markets, MarketMeta};
+use crate::data::pipeline::{EventSender, StateEvent};
+use crate::data::state::now_secs;
+use crate::data::types::MarketData;
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// An open market whose end date falls within `closing_horizon_hours`.
+#[derive(Clone, Serialize)]
+pub struct ClosingMarket {
+    pub question: String,
+    pub slug: String,
+    pub volume: f64,
+    /// Unix seconds.
+    pub end_date: u64,
+    pub meta: MarketMeta,
+}
+
+impl ClosingMarket {
+    fn from_market(market: MarketData) -> Option<Self> {
+        let meta = MarketMeta::from(&market);
+        Some(Self {
+            end_date: meta.end_date?,
+            volume: market.volume.parse().unwrap_or(0.0),
+            question: market.question,
+            slug: market.slug,
+            meta,
+        })
+    }
+}
+
+fn format_query_date(secs: u64) -> String {
+    DateTime::<Utc>::from_timestamp(secs as i64, 0)
+        .unwrap_or_default()
+        .to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Open markets ending between now and the horizon, soonest first.
+pub async fn get_closing_markets() -> Result<Vec<ClosingMarket>, MarketError> {
+    let now = now_secs();
+    let horizon = config::get().closing_horizon_hours * 60 * 60;
+    let url = format!(
+        "https://gamma-api.polymarket.com/markets?limit=500&active=true&closed=false&include_tag=true&end_date_min={}&end_date_max={}&order=endDate&ascending=true",
+        format_query_date(now),
+        format_query_date(now + horizon)
+    );
+
+    let markets = get_markets(&url).await?;
+    Ok(markets.into_iter().filter_map(ClosingMarket::from_market).collect())
+}
+
+/// Polls gamma for markets about to end and publishes them to the state actor. A failed
+/// poll leaves the last list in place.
+pub async fn run(events: EventSender) {
+    let mut interval = tokio::time::interval(REFRESH_INTERVAL);
+    loop {
+        interval.tick().await;
+        let closing = match get_closing_markets().await {
+            Ok(closing) => closing,
+            Err(e) => {
+                tracing::warn!("Failed to fetch closing markets: {}", e);
+                continue;
+            }
+        };
+        if events.send(StateEvent::ClosingMarkets(closing)).await.is_err() {
+            break;
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use crate::data::feed::Comment;
+use crate::data::get_market::MarketError;
+use crate::metrics::{self, Service};
+
+/// Most recent comments fetched for an event.
+const COMMENT_LIMIT: usize = 50;
+
+/// Latest comments on an event, newest first, with the authors' holdings in its markets.
+pub async fn get_comments(event_id: u64) -> Result<Vec<Comment>, MarketError> {
+    let url = format!(
+        "https://gamma-api.polymarket.com/comments?parent_entity_type=Event&parent_entity_id={}&limit={}&order=createdAt&ascending=false&get_positions=true",
+        event_id, COMMENT_LIMIT
+    );
+
+    let body = metrics::http_get(Service::Gamma, "comments", &url).await?.text().await?;
+    Ok(serde_json::from_str(&body)?)
+}
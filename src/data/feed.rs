@@ -0,0 +1,244 @@
+use chrono::DateTime;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::data::types::Payload;
+
+/// One message from the live-data websocket, routed by its topic and type.
+pub enum FeedMessage {
+    /// `activity/trades`
+    Trade(Payload),
+    /// `activity/orders_matched`, in the same shape as a trade.
+    OrdersMatched(Payload),
+    /// `comments/comment_created`
+    CommentCreated(Comment),
+    /// `comments/comment_removed`
+    CommentRemoved(Comment),
+    /// `comments/reaction_created`
+    ReactionCreated(Reaction),
+    /// `comments/reaction_removed`
+    ReactionRemoved(Reaction),
+    /// `crypto_prices/update`
+    CryptoPrice(CryptoPrice),
+    /// The server accepted a subscription to `topic`.
+    Subscribed { topic: String },
+    /// The server reported a problem, usually with a subscription.
+    Error(String),
+}
+
+impl FeedMessage {
+    /// Topic and type, for metrics and logs.
+    pub fn kind(&self) -> (&str, &str) {
+        match self {
+            FeedMessage::Trade(_) => ("activity", "trades"),
+            FeedMessage::OrdersMatched(_) => ("activity", "orders_matched"),
+            FeedMessage::CommentCreated(_) => ("comments", "comment_created"),
+            FeedMessage::CommentRemoved(_) => ("comments", "comment_removed"),
+            FeedMessage::ReactionCreated(_) => ("comments", "reaction_created"),
+            FeedMessage::ReactionRemoved(_) => ("comments", "reaction_removed"),
+            FeedMessage::CryptoPrice(_) => ("crypto_prices", "update"),
+            FeedMessage::Subscribed { topic } => (topic, "subscribed"),
+            FeedMessage::Error(_) => ("", "error"),
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum FeedError {
+    #[error("not a feed message: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("unknown message type {topic}/{kind}")]
+    Unknown { topic: String, kind: String },
+    #[error("bad {topic}/{kind} payload: {source}")]
+    Payload { topic: String, kind: String, source: serde_json::Error },
+}
+
+impl FeedError {
+    /// Topic and type of the failed message, empty when it had none.
+    pub fn kind(&self) -> (&str, &str) {
+        match self {
+            FeedError::Json(_) => ("", ""),
+            FeedError::Unknown { topic, kind } | FeedError::Payload { topic, kind, .. } => (topic, kind),
+        }
+    }
+}
+
+/// A comment on an event, from the feed or the gamma comments API.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Comment {
+    pub id: String,
+    #[serde(default)]
+    pub body: String,
+    #[serde(default)]
+    pub parent_entity_type: String,
+    #[serde(rename = "parentEntityID", default)]
+    pub parent_entity_id: u64,
+    /// Set on replies.
+    #[serde(rename = "parentCommentID")]
+    pub parent_comment_id: Option<String>,
+    #[serde(default)]
+    pub user_address: String,
+    /// RFC 3339.
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub reaction_count: u64,
+    pub profile: Option<CommentProfile>,
+}
+
+impl Comment {
+    /// Profile name, falling back to the pseudonym.
+    pub fn author_name(&self) -> Option<&str> {
+        let profile = self.profile.as_ref()?;
+        profile.name.as_deref().or(profile.pseudonym.as_deref()).filter(|name| !name.is_empty())
+    }
+
+    /// Unix seconds, 0 when the date is missing or malformed.
+    pub fn created_secs(&self) -> u64 {
+        self.created_at
+            .as_deref()
+            .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+            .map_or(0, |at| at.timestamp().max(0) as u64)
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentProfile {
+    pub name: Option<String>,
+    pub pseudonym: Option<String>,
+    pub proxy_wallet: Option<String>,
+    /// The author's holdings in the event's markets.
+    #[serde(default)]
+    pub positions: Vec<ProfilePosition>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfilePosition {
+    pub token_id: String,
+    /// Shares, scaled by 1e6 like on-chain amounts.
+    pub position_size: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Reaction {
+    pub id: String,
+    #[serde(rename = "commentID", default)]
+    pub comment_id: u64,
+    #[serde(default)]
+    pub reaction_type: String,
+    #[serde(default)]
+    pub user_address: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct CryptoPrice {
+    /// Lower-case pair such as `btcusdt`.
+    pub symbol: String,
+    /// Unix milliseconds.
+    pub timestamp: u64,
+    pub value: f64,
+}
+
+/// The fields every message shares; the payload is decoded once the type is known.
+#[derive(Deserialize)]
+struct Envelope {
+    #[serde(default)]
+    topic: String,
+    #[serde(rename = "type", default)]
+    kind: String,
+    #[serde(default)]
+    payload: serde_json::Value,
+    /// Present on error replies.
+    message: Option<String>,
+}
+
+impl Envelope {
+    fn payload<T: DeserializeOwned>(self) -> Result<T, FeedError> {
+        serde_json::from_value(self.payload).map_err(|source| FeedError::Payload {
+            topic: self.topic,
+            kind: self.kind,
+            source,
+        })
+    }
+}
+
+pub fn parse(text: &str) -> Result<FeedMessage, FeedError> {
+    let envelope: Envelope = serde_json::from_str(text)?;
+    let message = match (envelope.topic.as_str(), envelope.kind.as_str()) {
+        ("activity", "trades") => FeedMessage::Trade(envelope.payload()?),
+        ("activity", "orders_matched") => FeedMessage::OrdersMatched(envelope.payload()?),
+        ("comments", "comment_created") => FeedMessage::CommentCreated(envelope.payload()?),
+        ("comments", "comment_removed") => FeedMessage::CommentRemoved(envelope.payload()?),
+        ("comments", "reaction_created") => FeedMessage::ReactionCreated(envelope.payload()?),
+        ("comments", "reaction_removed") => FeedMessage::ReactionRemoved(envelope.payload()?),
+        ("crypto_prices", "update") => FeedMessage::CryptoPrice(envelope.payload()?),
+        (_, "subscribed") => FeedMessage::Subscribed { topic: envelope.topic },
+        (_, "error") | ("", _) if envelope.message.is_some() => {
+            FeedMessage::Error(envelope.message.unwrap_or_default())
+        }
+        _ => {
+            return Err(FeedError::Unknown { topic: envelope.topic, kind: envelope.kind });
+        }
+    };
+    Ok(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_trades() {
+        let text = r#"{"topic":"activity","type":"trades","payload":{"conditionId":"0x1","title":"Rain?","price":0.5,"size":10,"side":"BUY","outcome":"Yes","proxyWallet":"0xabc","slug":"rain","timestamp":1,"name":"bob"}}"#;
+        let Ok(FeedMessage::Trade(payload)) = parse(text) else {
+            panic!("not a trade");
+        };
+        assert_eq!((payload.slug.as_str(), payload.size, payload.name.as_deref()), ("rain", 10.0, Some("bob")));
+
+        let matched = text.replace("\"trades\"", "\"orders_matched\"");
+        assert!(matches!(parse(&matched), Ok(FeedMessage::OrdersMatched(_))));
+    }
+
+    #[test]
+    fn parses_comments_and_reactions() {
+        let text = r#"{"topic":"comments","type":"comment_created","payload":{"id":"7","body":"hi","parentEntityID":10,"createdAt":"2025-01-01T00:00:00Z","profile":{"pseudonym":"Quiet-Fox"}}}"#;
+        let Ok(FeedMessage::CommentCreated(comment)) = parse(text) else {
+            panic!("not a comment");
+        };
+        assert_eq!((comment.id.as_str(), comment.parent_entity_id), ("7", 10));
+        assert_eq!(comment.author_name(), Some("Quiet-Fox"));
+        assert_eq!(comment.created_secs(), 1_735_689_600);
+
+        let text = r#"{"topic":"comments","type":"reaction_removed","payload":{"id":"3","commentID":7}}"#;
+        let Ok(FeedMessage::ReactionRemoved(reaction)) = parse(text) else {
+            panic!("not a reaction");
+        };
+        assert_eq!(reaction.comment_id, 7);
+    }
+
+    #[test]
+    fn parses_control_messages() {
+        let subscribed = parse(r#"{"topic":"activity","type":"subscribed"}"#);
+        assert!(matches!(subscribed, Ok(FeedMessage::Subscribed { topic }) if topic == "activity"));
+
+        let error = parse(r#"{"message":"Invalid subscription"}"#);
+        assert!(matches!(error, Ok(FeedMessage::Error(message)) if message == "Invalid subscription"));
+    }
+
+    #[test]
+    fn reports_what_failed() {
+        assert!(matches!(parse("not json"), Err(FeedError::Json(_))));
+
+        let unknown = parse(r#"{"topic":"weird","type":"thing","payload":{}}"#).err().unwrap();
+        assert!(matches!(unknown, FeedError::Unknown { .. }));
+        assert_eq!(unknown.kind(), ("weird", "thing"));
+
+        let bad = parse(r#"{"topic":"activity","type":"trades","payload":{"bad":1}}"#).err().unwrap();
+        assert!(matches!(bad, FeedError::Payload { .. }));
+        assert_eq!(bad.kind(), ("activity", "trades"));
+    }
+}
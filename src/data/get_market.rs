@@ -1,5 +1,6 @@
 use thiserror::Error;
 use crate::data::types::MarketSpecificDetails;
+use crate::metrics::{self, Service};
 
 #[derive(Error, Debug)]
 pub enum MarketError {
@@ -14,19 +15,16 @@ pub enum MarketError {
 pub async fn get_market_from_slug(market_slug: &str) -> Result<MarketSpecificDetails, MarketError> {
     let url = format!("https://gamma-api.polymarket.com/markets/slug/{}", market_slug);
 
-    let response = reqwest::get(&url).await?;
+    let response = metrics::http_get(Service::Gamma, "markets/slug", &url).await?;
     let status = response.status();
 
-        let body = response.text().await?;
-
-
+    let body = response.text().await?;
 
     let data: MarketSpecificDetails = serde_json::from_str(&body)
         .map_err(|e| {
-            eprintln!("JSON parse error: {}", e);
-            eprintln!("Raw response: {}", body);
+            tracing::warn!(slug = market_slug, %status, "Cannot parse market: {}", e);
+            tracing::debug!(slug = market_slug, "Market response: {}", body);
             MarketError::Json(e)
-
         })?;
 
     Ok(data)
@@ -0,0 +1,13 @@
+use crate::data::get_market::MarketError;
+use crate::data::state::Trade;
+use crate::data::types::Payload;
+use crate::metrics::{self, Service};
+
+pub async fn get_trader_trades(address: &str) -> Result<Vec<Trade>, MarketError> {
+    let url = format!("https://data-api.polymarket.com/trades?user={}&limit=100", address);
+
+    let body = metrics::http_get(Service::DataApi, "trades", &url).await?.text().await?;
+    let payloads: Vec<Payload> = serde_json::from_str(&body)?;
+
+    Ok(payloads.into_iter().map(Trade::from).collect())
+}
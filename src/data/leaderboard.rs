@@ -0,0 +1,215 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap};
+
+/// f64 wrapper with a total order so volumes can live in a `BTreeSet`.
+#[derive(Clone, Copy, PartialEq)]
+struct Volume(f64);
+
+impl Eq for Volume {}
+
+impl PartialOrd for Volume {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Volume {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed so iteration runs from the highest volume down.
+        other.0.total_cmp(&self.0)
+    }
+}
+
+struct Entry {
+    volume: f64,
+    count: u64,
+    identifier: String,
+    last_seen: u64,
+    /// Sequence number of the first trade counted, so trades from before an eviction
+    /// are not taken back out of a re-added entry.
+    since: u64,
+}
+
+/// One row of a leaderboard, borrowed from it.
+pub struct Ranked<'a> {
+    pub key: &'a str,
+    pub volume: f64,
+    pub count: u64,
+    pub identifier: &'a str,
+}
+
+/// A capped volume ranking that is kept sorted as it is updated.
+///
+/// Updates and evictions are O(log n) and reading the top N is O(N), so nothing
+/// is re-sorted per frame. When the cap is reached the least recently updated
+/// entry is dropped.
+pub struct Leaderboard {
+    entries: HashMap<String, Entry>,
+    ranking: BTreeSet<(Volume, String)>,
+    recency: BTreeSet<(u64, String)>,
+    capacity: usize,
+    clock: u64,
+    inserted: u64,
+}
+
+impl Leaderboard {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            ranking: BTreeSet::new(),
+            recency: BTreeSet::new(),
+            capacity: capacity.max(1),
+            clock: 0,
+            inserted: 0,
+        }
+    }
+
+    /// Adds the volume of trade number `seq` to `key`, returning the identifier of any
+    /// entry evicted to make room.
+    pub fn add(&mut self, key: &str, amount: f64, identifier: &str, seq: u64) -> Option<String> {
+        self.clock += 1;
+        let mut evicted = None;
+        match self.entries.get_mut(key) {
+            Some(entry) => {
+                self.ranking.remove(&(Volume(entry.volume), key.to_string()));
+                self.recency.remove(&(entry.last_seen, key.to_string()));
+                entry.volume += amount;
+                entry.count += 1;
+                entry.last_seen = self.clock;
+                self.ranking.insert((Volume(entry.volume), key.to_string()));
+                self.recency.insert((entry.last_seen, key.to_string()));
+            }
+            None => {
+                if self.entries.len() >= self.capacity {
+                    evicted = self.evict_coldest();
+                }
+                self.entries.insert(key.to_string(), Entry {
+                    volume: amount,
+                    count: 1,
+                    identifier: identifier.to_string(),
+                    last_seen: self.clock,
+                    since: seq,
+                });
+                self.ranking.insert((Volume(amount), key.to_string()));
+                self.recency.insert((self.clock, key.to_string()));
+                self.inserted += 1;
+            }
+        }
+        evicted
+    }
+
+    /// Takes the volume of trade number `seq` back out, dropping the entry once nothing
+    /// is left. Trades from before the entry was (re-)added are ignored.
+    pub fn subtract(&mut self, key: &str, amount: f64, seq: u64) {
+        let Some(entry) = self.entries.get_mut(key).filter(|e| seq >= e.since) else {
+            return;
+        };
+        self.ranking.remove(&(Volume(entry.volume), key.to_string()));
+        entry.volume -= amount;
+        entry.count = entry.count.saturating_sub(1);
+        if entry.volume <= f64::EPSILON || entry.count == 0 {
+            self.recency.remove(&(entry.last_seen, key.to_string()));
+            self.entries.remove(key);
+        } else {
+            self.ranking.insert((Volume(entry.volume), key.to_string()));
+        }
+    }
+
+    fn evict_coldest(&mut self) -> Option<String> {
+        let (_, key) = self.recency.pop_first()?;
+        let entry = self.entries.remove(&key)?;
+        self.ranking.remove(&(Volume(entry.volume), key));
+        Some(entry.identifier)
+    }
+
+    /// The `n` highest-volume entries.
+    pub fn top(&self, n: usize) -> impl Iterator<Item = Ranked<'_>> {
+        self.ranking.iter().take(n).filter_map(|(volume, key)| {
+            let entry = self.entries.get(key)?;
+            Some(Ranked {
+                key: key.as_str(),
+                volume: volume.0,
+                count: entry.count,
+                identifier: entry.identifier.as_str(),
+            })
+        })
+    }
+
+    pub fn get(&self, key: &str) -> Option<f64> {
+        self.entries.get(key).map(|e| e.volume)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Number of distinct keys ever inserted, including ones since evicted.
+    pub fn inserted(&self) -> u64 {
+        self.inserted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(board: &Leaderboard) -> Vec<(String, f64)> {
+        board.top(usize::MAX).map(|e| (e.key.to_string(), e.volume)).collect()
+    }
+
+    #[test]
+    fn ranks_by_accumulated_volume() {
+        let mut board = Leaderboard::new(10);
+        board.add("a", 5.0, "slug-a", 0);
+        board.add("b", 8.0, "slug-b", 1);
+        board.add("a", 4.0, "slug-a", 2);
+
+        assert_eq!(keys(&board), vec![("a".to_string(), 9.0), ("b".to_string(), 8.0)]);
+        let top = board.top(1).next().unwrap();
+        assert_eq!((top.key, top.count, top.identifier), ("a", 2, "slug-a"));
+        assert_eq!(board.inserted(), 2);
+    }
+
+    #[test]
+    fn subtract_reranks_and_drops_empty_entries() {
+        let mut board = Leaderboard::new(10);
+        board.add("a", 5.0, "", 0);
+        board.add("b", 3.0, "", 1);
+        board.add("a", 1.0, "", 2);
+
+        board.subtract("a", 5.0, 0);
+        assert_eq!(keys(&board), vec![("b".to_string(), 3.0), ("a".to_string(), 1.0)]);
+
+        board.subtract("a", 1.0, 2);
+        assert_eq!(board.get("a"), None);
+        assert_eq!(board.len(), 1);
+    }
+
+    #[test]
+    fn evicts_the_least_recently_updated_entry() {
+        let mut board = Leaderboard::new(2);
+        board.add("a", 100.0, "slug-a", 0);
+        board.add("b", 1.0, "slug-b", 1);
+        board.add("a", 1.0, "slug-a", 2);
+
+        assert_eq!(board.add("c", 2.0, "slug-c", 3), Some("slug-b".to_string()));
+        assert_eq!(board.get("b"), None);
+        assert_eq!(board.len(), 2);
+        assert_eq!(board.inserted(), 3);
+    }
+
+    #[test]
+    fn ignores_trades_from_before_a_re_add() {
+        let mut board = Leaderboard::new(1);
+        board.add("a", 10.0, "", 0);
+        board.add("b", 1.0, "", 1);
+        board.add("a", 4.0, "", 2);
+
+        // Trade 0 was counted by the evicted entry, not the current one.
+        board.subtract("a", 10.0, 0);
+        assert_eq!(board.get("a"), Some(4.0));
+
+        board.subtract("a", 4.0, 2);
+        assert_eq!(board.get("a"), None);
+    }
+}
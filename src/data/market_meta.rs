@@ -0,0 +1,104 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use chrono::DateTime;
+use serde::Serialize;
+
+use crate::data::get_market::MarketError;
+use crate::data::pipeline::{EventSender, StateEvent};
+use crate::data::state::SharedState;
+use crate::data::types::MarketData;
+use crate::metrics::{self, Service};
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(15);
+/// Slugs looked up per gamma request.
+const BATCH_SIZE: usize = 50;
+/// Forget which slugs were asked for past this many so the set stays bounded.
+const REQUESTED_CAP: usize = 10_000;
+
+/// Tags and end date of a market, as listed by gamma.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct MarketMeta {
+    pub tags: Vec<String>,
+    /// Unix seconds.
+    pub end_date: Option<u64>,
+}
+
+impl From<&MarketData> for MarketMeta {
+    fn from(market: &MarketData) -> Self {
+        let mut tags: Vec<String> = market
+            .tags
+            .iter()
+            .flatten()
+            .map(|t| t.label.clone())
+            .collect();
+        if let Some(category) = &market.category
+            && !tags.iter().any(|t| t.eq_ignore_ascii_case(category))
+        {
+            tags.push(category.clone());
+        }
+        Self {
+            tags,
+            end_date: market.end_date.as_deref().and_then(parse_timestamp),
+        }
+    }
+}
+
+/// Parses a gamma RFC 3339 date into unix seconds.
+pub fn parse_timestamp(date: &str) -> Option<u64> {
+    DateTime::parse_from_rfc3339(date).ok().map(|d| d.timestamp().max(0) as u64)
+}
+
+/// Fetches a gamma market listing.
+pub async fn get_markets(url: &str) -> Result<Vec<MarketData>, MarketError> {
+    let text = metrics::http_get(Service::Gamma, "markets", url).await?.text().await?;
+    Ok(serde_json::from_str(&text)?)
+}
+
+pub async fn get_market_meta(slugs: &[String]) -> Vec<(String, MarketMeta)> {
+    let query: String = slugs.iter().map(|s| format!("&slug={}", s)).collect();
+    let url = format!("https://gamma-api.polymarket.com/markets?include_tag=true&limit={}{}", slugs.len(), query);
+
+    match get_markets(&url).await {
+        Ok(markets) => markets.iter().map(|m| (m.slug.clone(), MarketMeta::from(m))).collect(),
+        Err(e) => {
+            tracing::warn!("Failed to look up tags for {} markets: {}", slugs.len(), e);
+            vec![]
+        }
+    }
+}
+
+/// Looks up tags and end dates for leaderboard markets that do not have them yet.
+pub async fn run(events: EventSender, state: SharedState) {
+    let mut interval = tokio::time::interval(REFRESH_INTERVAL);
+    // Slugs already asked for, so markets gamma does not know are not retried forever.
+    let mut requested: HashSet<String> = HashSet::new();
+    loop {
+        interval.tick().await;
+        let missing: Vec<String> = {
+            let snapshot = state.borrow();
+            snapshot
+                .windows
+                .iter()
+                .flat_map(|(_, w)| w.top_markets.iter())
+                .filter(|m| m.meta.is_none() && !requested.contains(&m.identifier))
+                .map(|m| m.identifier.clone())
+                .collect::<HashSet<_>>()
+                .into_iter()
+                .take(BATCH_SIZE)
+                .collect()
+        };
+        if missing.is_empty() {
+            continue;
+        }
+        if requested.len() > REQUESTED_CAP {
+            requested.clear();
+        }
+        requested.extend(missing.iter().cloned());
+
+        let found = get_market_meta(&missing).await;
+        if !found.is_empty() && events.send(StateEvent::MarketMeta(found)).await.is_err() {
+            break;
+        }
+    }
+}
@@ -1,5 +1,20 @@
 pub mod ws;
+pub mod order_book;
+pub mod feed;
+pub mod recorder;
+pub mod replay;
+pub mod trade_db;
 pub mod state;
+pub mod pipeline;
+pub mod leaderboard;
+pub mod prices;
 pub mod types;
 pub mod new_markets;
-pub mod get_market;
\ No newline at end of file
+pub mod closing_markets;
+pub mod market_meta;
+pub mod view;
+pub mod get_market;
+pub mod get_trader;
+pub mod positions;
+pub mod comments;
+pub mod price_history;
\ No newline at end of file
@@ -1,18 +1,56 @@
-use crate::data::{state::SharedState, types::MarketData};
-
-pub async fn get_new_markets() -> Vec<(String, String)> {
-    let mut output = Vec::new();
-    let url = "https://gamma-api.polymarket.com/markets?limit=1000&closed=false&order=createdAt&ascending=false";
-    
-    if let Ok(resp) = reqwest::get(url).await {
-        if let Ok(text) = resp.text().await {
-            if let Ok(markets) = serde_json::from_str::<Vec<MarketData>>(&text) {
-                for market in markets {
-                    output.push((market.question, market.volume));
-                }
-            }
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::data::market_meta::{get_markets, parse_timestamp, MarketMeta};
+use crate::data::pipeline::{EventSender, StateEvent};
+use crate::data::types::MarketData;
+use crate::metrics::{self, Service};
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Serialize)]
+pub struct NewMarket {
+    pub question: String,
+    pub slug: String,
+    pub volume: f64,
+    /// Unix seconds.
+    pub created_at: Option<u64>,
+    pub meta: MarketMeta,
+}
+
+impl From<MarketData> for NewMarket {
+    fn from(market: MarketData) -> Self {
+        Self {
+            meta: MarketMeta::from(&market),
+            created_at: market.created_at.as_deref().and_then(parse_timestamp),
+            volume: market.volume.parse().unwrap_or(0.0),
+            question: market.question,
+            slug: market.slug,
+        }
+    }
+}
+
+pub async fn get_new_markets() -> Vec<NewMarket> {
+    let url = "https://gamma-api.polymarket.com/markets?limit=1000&closed=false&include_tag=true&order=createdAt&ascending=false";
+
+    match get_markets(url).await {
+        Ok(markets) => markets.into_iter().map(NewMarket::from).collect(),
+        Err(e) => {
+            tracing::warn!("Failed to fetch new markets: {}", e);
+            vec![]
+        }
+    }
+}
+
+/// Polls gamma for newly created markets and publishes them to the state actor.
+pub async fn run(events: EventSender) {
+    let mut interval = tokio::time::interval(REFRESH_INTERVAL);
+    loop {
+        interval.tick().await;
+        let new_markets = get_new_markets().await;
+        if events.send(StateEvent::NewMarkets(new_markets)).await.is_err() {
+            break;
         }
     }
-    
-    output
-}
\ No newline at end of file
+}
@@ -0,0 +1,235 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use serde_json::json;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+const WS_URL: &str = "wss://ws-subscriptions-clob.polymarket.com/ws/market";
+/// The market channel drops connections that stay quiet for longer than this.
+const PING_INTERVAL: Duration = Duration::from_secs(10);
+const MIN_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+/// Prices are keyed in ten-thousandths so levels can be matched exactly.
+const PRICE_SCALE: f64 = 10_000.0;
+
+/// Resting size per price level of one outcome token.
+#[derive(Clone, Default)]
+pub struct Book {
+    bids: BTreeMap<u32, f64>,
+    asks: BTreeMap<u32, f64>,
+}
+
+impl Book {
+    /// The best `n` bids as (price, size), highest first.
+    pub fn bids(&self, n: usize) -> Vec<(f64, f64)> {
+        self.bids.iter().rev().take(n).map(|(p, s)| (*p as f64 / PRICE_SCALE, *s)).collect()
+    }
+
+    /// The best `n` asks as (price, size), lowest first.
+    pub fn asks(&self, n: usize) -> Vec<(f64, f64)> {
+        self.asks.iter().take(n).map(|(p, s)| (*p as f64 / PRICE_SCALE, *s)).collect()
+    }
+
+    /// Sets the size at a price, removing the level when the size is zero.
+    fn set(&mut self, buy: bool, price: &str, size: &str) {
+        let (Ok(price), Ok(size)) = (price.parse::<f64>(), size.parse::<f64>()) else {
+            return;
+        };
+        let side = if buy { &mut self.bids } else { &mut self.asks };
+        let key = (price * PRICE_SCALE).round() as u32;
+        if size > 0.0 {
+            side.insert(key, size);
+        } else {
+            side.remove(&key);
+        }
+    }
+}
+
+/// Books by token id.
+pub type Books = HashMap<String, Book>;
+
+/// A live order book subscription for a market's outcome tokens. The connection is
+/// closed when the subscription is dropped.
+pub struct OrderBookFeed {
+    pub token_ids: Vec<String>,
+    books: watch::Receiver<Arc<Books>>,
+    task: JoinHandle<()>,
+}
+
+impl OrderBookFeed {
+    pub fn subscribe(token_ids: Vec<String>) -> Self {
+        let (books_tx, books) = watch::channel(Arc::new(Books::new()));
+        let task = tokio::spawn(run(token_ids.clone(), books_tx));
+        Self { token_ids, books, task }
+    }
+
+    pub fn book(&self, token_id: &str) -> Option<Book> {
+        self.books.borrow().get(token_id).cloned()
+    }
+}
+
+impl Drop for OrderBookFeed {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[derive(Deserialize)]
+struct Level {
+    price: String,
+    size: String,
+}
+
+#[derive(Deserialize)]
+struct PriceChange {
+    /// Missing in the older format, which puts it on the message.
+    asset_id: Option<String>,
+    price: String,
+    size: String,
+    side: String,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "event_type", rename_all = "snake_case")]
+enum MarketEvent {
+    /// The whole book, sent on subscribing and after trades.
+    Book {
+        asset_id: String,
+        #[serde(default, alias = "buys")]
+        bids: Vec<Level>,
+        #[serde(default, alias = "sells")]
+        asks: Vec<Level>,
+    },
+    /// Levels whose size changed.
+    PriceChange {
+        asset_id: Option<String>,
+        #[serde(default, alias = "changes")]
+        price_changes: Vec<PriceChange>,
+    },
+    #[serde(other)]
+    Other,
+}
+
+/// The channel sends single events and arrays of them.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Batch {
+    Many(Vec<MarketEvent>),
+    One(MarketEvent),
+}
+
+/// Applies one message to `books`, returning whether anything changed.
+fn apply(books: &mut Books, text: &str) -> bool {
+    let events = match serde_json::from_str(text) {
+        Ok(Batch::Many(events)) => events,
+        Ok(Batch::One(event)) => vec![event],
+        // PONG replies and anything unexpected.
+        Err(_) => return false,
+    };
+    let mut changed = false;
+    for event in events {
+        match event {
+            MarketEvent::Book { asset_id, bids, asks } => {
+                let mut book = Book::default();
+                for level in bids {
+                    book.set(true, &level.price, &level.size);
+                }
+                for level in asks {
+                    book.set(false, &level.price, &level.size);
+                }
+                books.insert(asset_id, book);
+                changed = true;
+            }
+            MarketEvent::PriceChange { asset_id, price_changes } => {
+                for change in price_changes {
+                    let Some(asset) = change.asset_id.as_ref().or(asset_id.as_ref()) else {
+                        continue;
+                    };
+                    let buy = change.side.eq_ignore_ascii_case("buy");
+                    books.entry(asset.clone()).or_default().set(buy, &change.price, &change.size);
+                    changed = true;
+                }
+            }
+            MarketEvent::Other => {}
+        }
+    }
+    changed
+}
+
+/// Keeps the subscription up, reconnecting with backoff, until it is aborted.
+async fn run(token_ids: Vec<String>, books: watch::Sender<Arc<Books>>) {
+    let mut delay = MIN_RECONNECT_DELAY;
+    loop {
+        match stream(&token_ids, &books).await {
+            Ok(()) => delay = MIN_RECONNECT_DELAY,
+            Err(e) => tracing::debug!("Order book connection failed: {}", e),
+        }
+        tokio::time::sleep(delay).await;
+        delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+    }
+}
+
+/// Runs one connection until the server closes it.
+async fn stream(token_ids: &[String], books: &watch::Sender<Arc<Books>>) -> anyhow::Result<()> {
+    let (ws_stream, _) = connect_async(WS_URL).await?;
+    let (mut write, mut read) = ws_stream.split();
+    let sub_req = json!({ "assets_ids": token_ids, "type": "market" });
+    write.send(Message::Text(sub_req.to_string().into())).await?;
+
+    // Each connection starts over from the books the server sends on subscribing.
+    let mut current = Books::new();
+    let mut ping = tokio::time::interval(PING_INTERVAL);
+    loop {
+        tokio::select! {
+            _ = ping.tick() => write.send(Message::Text("PING".into())).await?,
+            msg = read.next() => {
+                let Some(msg) = msg else {
+                    return Ok(());
+                };
+                if let Ok(text) = msg?.into_text()
+                    && apply(&mut current, &text)
+                {
+                    books.send_replace(Arc::new(current.clone()));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn applies_books_and_price_changes() {
+        let mut books = Books::new();
+        let snapshot = r#"[{"event_type":"book","asset_id":"yes","market":"0x1",
+            "bids":[{"price":"0.48","size":"30"},{"price":"0.49","size":"20"}],
+            "asks":[{"price":"0.52","size":"25"},{"price":"0.51","size":"10"}]}]"#;
+        assert!(apply(&mut books, snapshot));
+        let book = &books["yes"];
+        assert_eq!(book.bids(5), vec![(0.49, 20.0), (0.48, 30.0)]);
+        assert_eq!(book.asks(1), vec![(0.51, 10.0)]);
+
+        let change = r#"{"event_type":"price_change","market":"0x1","price_changes":[
+            {"asset_id":"yes","price":"0.49","size":"0","side":"BUY"},
+            {"asset_id":"yes","price":"0.5","size":"5","side":"SELL"}]}"#;
+        assert!(apply(&mut books, change));
+        let book = &books["yes"];
+        assert_eq!(book.bids(5), vec![(0.48, 30.0)]);
+        assert_eq!(book.asks(5), vec![(0.5, 5.0), (0.51, 10.0), (0.52, 25.0)]);
+    }
+
+    #[test]
+    fn ignores_other_messages() {
+        let mut books = Books::new();
+        assert!(!apply(&mut books, "PONG"));
+        assert!(!apply(&mut books, r#"{"event_type":"tick_size_change","asset_id":"yes"}"#));
+        assert!(books.is_empty());
+    }
+}
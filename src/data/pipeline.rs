@@ -0,0 +1,239 @@
+use std::mem;
+use std::sync::{Arc, mpsc as std_mpsc};
+use std::time::Duration;
+use tokio::sync::mpsc::error::SendError;
+use tokio::sync::{mpsc, oneshot, watch};
+use tokio::time::MissedTickBehavior;
+
+use crate::data::closing_markets::ClosingMarket;
+use crate::data::market_meta::MarketMeta;
+use crate::data::new_markets::NewMarket;
+use crate::data::prices::MarketPrices;
+use crate::config;
+use crate::metrics;
+use crate::data::feed::Comment;
+use crate::data::state::{now_secs, AppState, LiveComments, SharedState, Snapshot, Trade};
+use crate::data::trade_db::TradeDb;
+use crate::data::view::ListView;
+
+const EVENT_BUFFER: usize = 4096;
+const QUERY_BUFFER: usize = 64;
+const PUBLISH_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Everything that can change `AppState`. Producers never touch the state directly.
+pub enum StateEvent {
+    Trade(Trade),
+    NewMarkets(Vec<NewMarket>),
+    ClosingMarkets(Vec<ClosingMarket>),
+    /// Tags and end dates looked up for markets by slug.
+    MarketMeta(Vec<(String, MarketMeta)>),
+    CommentCreated(Comment),
+    CommentRemoved { id: String },
+    Reaction { comment_id: u64, added: bool },
+}
+
+/// Lookups of state that is not part of the published snapshot. They have their own
+/// channel, served ahead of `StateEvent`s, so pages never wait behind the trade feed.
+pub enum StateQuery {
+    /// A trader's session volume.
+    TraderVolume { address: String, reply: oneshot::Sender<f64> },
+    /// The live price statistics of one market.
+    MarketPrices { slug: String, reply: oneshot::Sender<Option<MarketPrices>> },
+    /// The trades of one market still held in the rolling log.
+    MarketTrades { slug: String, reply: oneshot::Sender<Vec<Trade>> },
+    /// The live comments on one event.
+    EventComments { event_id: u64, reply: oneshot::Sender<LiveComments> },
+}
+
+/// Write side of the state pipeline, for events, queries and the dashboard's list view.
+#[derive(Clone)]
+pub struct EventSender {
+    events: mpsc::Sender<StateEvent>,
+    queries: mpsc::Sender<StateQuery>,
+    view: watch::Sender<ListView>,
+}
+
+impl EventSender {
+    pub async fn send(&self, event: StateEvent) -> Result<(), SendError<StateEvent>> {
+        self.events.send(event).await
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.events.is_closed()
+    }
+
+    /// Sets the filter and sort the snapshot's top lists are built with. Never waits,
+    /// and only wakes the actor when the view actually changed.
+    pub fn set_view(&self, view: ListView) {
+        self.view.send_if_modified(|current| {
+            let changed = *current != view;
+            *current = view;
+            changed
+        });
+    }
+
+    /// Sends `query` and waits for the reply, or `None` if the actor has stopped.
+    async fn ask<T>(&self, query: impl FnOnce(oneshot::Sender<T>) -> StateQuery) -> Option<T> {
+        let (reply, response) = oneshot::channel();
+        self.queries.send(query(reply)).await.ok()?;
+        response.await.ok()
+    }
+}
+
+/// Starts the state actor, returning the channel ingestion publishes events on and
+/// the receiver that always holds the latest snapshot. With a database, the state
+/// starts from the recently saved trades and every new trade is saved.
+pub fn spawn(db: Option<TradeDb>) -> (EventSender, SharedState) {
+    let (event_tx, event_rx) = mpsc::channel(EVENT_BUFFER);
+    let (query_tx, query_rx) = mpsc::channel(QUERY_BUFFER);
+    let (view_tx, view_rx) = watch::channel(ListView::default());
+    let mut state = AppState::new();
+    let saver = db.map(|db| {
+        rehydrate(&mut state, &db);
+        db.spawn_writer()
+    });
+    let (snapshot_tx, snapshot_rx) = watch::channel(Arc::new(state.snapshot()));
+
+    tokio::spawn(run(state, event_rx, query_rx, view_rx, snapshot_tx, saver));
+
+    (EventSender { events: event_tx, queries: query_tx, view: view_tx }, snapshot_rx)
+}
+
+/// Replays the last `rehydrate_hours` of saved trades into the state.
+fn rehydrate(state: &mut AppState, db: &TradeDb) {
+    let since = now_secs().saturating_sub(config::get().rehydrate_hours * 60 * 60);
+    match db.load_since(since) {
+        Ok(trades) => {
+            for trade in trades {
+                state.add_trade(trade);
+            }
+        }
+        Err(e) => tracing::error!("Failed to load saved trades: {}", e),
+    }
+}
+
+async fn run(
+    mut state: AppState,
+    mut events: mpsc::Receiver<StateEvent>,
+    mut queries: mpsc::Receiver<StateQuery>,
+    mut view: watch::Receiver<ListView>,
+    snapshots: watch::Sender<Arc<Snapshot>>,
+    saver: Option<std_mpsc::Sender<Vec<Trade>>>,
+) {
+    let mut publish = tokio::time::interval(PUBLISH_INTERVAL);
+    // A slow snapshot should not be followed by a burst of catch-up ticks.
+    publish.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    let mut dirty = false;
+    // Trades waiting to be handed to the database writer, sent once per publish tick.
+    let mut unsaved: Vec<Trade> = Vec::new();
+
+    loop {
+        tokio::select! {
+            // Queries first, so a busy feed does not hold up the pages asking them, and
+            // the publish tick ahead of events, so a burst of them cannot hold up snapshots,
+            // expiry or saving.
+            biased;
+            Some(query) = queries.recv() => answer(&state, query),
+            _ = publish.tick() => {
+                if let Some(saver) = &saver
+                    && !unsaved.is_empty()
+                {
+                    let _ = saver.send(mem::take(&mut unsaved));
+                }
+                dirty |= state.evict_expired(now_secs());
+                if dirty {
+                    metrics::get().record_state(&state);
+                    snapshots.send_replace(Arc::new(state.snapshot()));
+                    dirty = false;
+                }
+            }
+            Ok(()) = view.changed() => {
+                state.set_view(view.borrow_and_update().clone());
+                dirty = true;
+            }
+            event = events.recv() => {
+                let Some(event) = event else {
+                    break;
+                };
+                if saver.is_some()
+                    && let StateEvent::Trade(trade) = &event
+                {
+                    unsaved.push(trade.clone());
+                }
+                dirty |= apply(&mut state, event);
+            }
+        }
+    }
+}
+
+/// Applies one event, returning whether the published snapshot is now stale.
+fn apply(state: &mut AppState, event: StateEvent) -> bool {
+    match event {
+        StateEvent::Trade(trade) => {
+            state.add_trade(trade);
+            true
+        }
+        StateEvent::NewMarkets(markets) => {
+            state.set_new_markets(markets);
+            true
+        }
+        StateEvent::ClosingMarkets(markets) => {
+            state.set_closing_markets(markets);
+            true
+        }
+        StateEvent::MarketMeta(meta) => {
+            state.set_market_meta(meta);
+            true
+        }
+        // Comments are not part of the snapshot; market pages ask for them.
+        StateEvent::CommentCreated(comment) => {
+            state.add_comment(comment);
+            false
+        }
+        StateEvent::CommentRemoved { id } => {
+            state.remove_comment(id);
+            false
+        }
+        StateEvent::Reaction { comment_id, added } => {
+            state.react(comment_id, added);
+            false
+        }
+    }
+}
+
+fn answer(state: &AppState, query: StateQuery) {
+    match query {
+        StateQuery::TraderVolume { address, reply } => {
+            let _ = reply.send(state.trader_volume(&address));
+        }
+        StateQuery::MarketPrices { slug, reply } => {
+            let _ = reply.send(state.market_prices(&slug));
+        }
+        StateQuery::MarketTrades { slug, reply } => {
+            let _ = reply.send(state.market_trades(&slug));
+        }
+        StateQuery::EventComments { event_id, reply } => {
+            let _ = reply.send(state.event_comments(event_id));
+        }
+    }
+}
+
+/// Looks up a trader's session volume through the actor.
+pub async fn trader_volume(events: &EventSender, address: &str) -> Option<f64> {
+    events.ask(|reply| StateQuery::TraderVolume { address: address.to_string(), reply }).await
+}
+
+/// Looks up the live price statistics of a market through the actor.
+pub async fn market_prices(events: &EventSender, slug: &str) -> Option<MarketPrices> {
+    events.ask(|reply| StateQuery::MarketPrices { slug: slug.to_string(), reply }).await.flatten()
+}
+
+/// Fetches the recent trades of a market through the actor.
+pub async fn market_trades(events: &EventSender, slug: &str) -> Vec<Trade> {
+    events.ask(|reply| StateQuery::MarketTrades { slug: slug.to_string(), reply }).await.unwrap_or_default()
+}
+
+/// Fetches the comments on an event seen on the live feed through the actor.
+pub async fn event_comments(events: &EventSender, event_id: u64) -> LiveComments {
+    events.ask(|reply| StateQuery::EventComments { event_id, reply }).await.unwrap_or_default()
+}
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+use crate::data::get_market::MarketError;
+use crate::metrics::{self, Service};
+
+/// One outcome holding of a wallet, as listed by the data API.
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct Position {
+    pub title: String,
+    pub slug: String,
+    pub outcome: String,
+    pub size: f64,
+    #[serde(default)]
+    pub avg_price: f64,
+    #[serde(default)]
+    pub cur_price: f64,
+    #[serde(default)]
+    pub current_value: f64,
+    #[serde(default)]
+    pub cash_pnl: f64,
+    #[serde(default)]
+    pub percent_pnl: f64,
+}
+
+pub async fn get_positions(address: &str) -> Result<Vec<Position>, MarketError> {
+    let url = format!("https://data-api.polymarket.com/positions?user={}&limit=500", address);
+
+    let body = metrics::http_get(Service::DataApi, "positions", &url).await?.text().await?;
+    Ok(serde_json::from_str(&body)?)
+}
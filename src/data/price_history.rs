@@ -0,0 +1,72 @@
+use serde::Deserialize;
+
+use crate::data::get_market::MarketError;
+use crate::metrics::{self, Service};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum HistoryRange {
+    OneHour,
+    SixHours,
+    OneDay,
+    OneWeek,
+    Max,
+}
+
+impl HistoryRange {
+    pub fn label(&self) -> &'static str {
+        match self {
+            HistoryRange::OneHour => "1h",
+            HistoryRange::SixHours => "6h",
+            HistoryRange::OneDay => "1d",
+            HistoryRange::OneWeek => "1w",
+            HistoryRange::Max => "max",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            HistoryRange::OneHour => HistoryRange::SixHours,
+            HistoryRange::SixHours => HistoryRange::OneDay,
+            HistoryRange::OneDay => HistoryRange::OneWeek,
+            HistoryRange::OneWeek => HistoryRange::Max,
+            HistoryRange::Max => HistoryRange::OneHour,
+        }
+    }
+
+    /// Resolution of the returned series in minutes.
+    fn fidelity(&self) -> u32 {
+        match self {
+            HistoryRange::OneHour => 1,
+            HistoryRange::SixHours => 5,
+            HistoryRange::OneDay => 15,
+            HistoryRange::OneWeek => 60,
+            HistoryRange::Max => 1440,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct HistoryPoint {
+    t: u64,
+    p: f64,
+}
+
+#[derive(Deserialize)]
+struct HistoryResponse {
+    history: Vec<HistoryPoint>,
+}
+
+/// Fetches the CLOB price series for one outcome token as `(unix seconds, price)` points.
+pub async fn get_price_history(token_id: &str, range: HistoryRange) -> Result<Vec<(f64, f64)>, MarketError> {
+    let url = format!(
+        "https://clob.polymarket.com/prices-history?market={}&interval={}&fidelity={}",
+        token_id,
+        range.label(),
+        range.fidelity()
+    );
+
+    let body = metrics::http_get(Service::Clob, "prices-history", &url).await?.text().await?;
+    let response: HistoryResponse = serde_json::from_str(&body)?;
+
+    Ok(response.history.into_iter().map(|point| (point.t as f64, point.p)).collect())
+}
@@ -0,0 +1,116 @@
+use std::collections::VecDeque;
+
+use serde::Serialize;
+
+use crate::data::state::Trade;
+
+/// How many minutes of per-minute volume each market keeps for sparklines.
+pub const ACTIVITY_MINUTES: usize = 20;
+
+/// Session price statistics for one outcome of a market, built from the trade feed.
+#[derive(Clone, Debug, Serialize)]
+pub struct OutcomeStats {
+    pub outcome: String,
+    pub last: f64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub trades: u64,
+    pub last_trade_at: u64,
+    #[serde(skip)]
+    notional: f64,
+    #[serde(skip)]
+    shares: f64,
+}
+
+impl OutcomeStats {
+    fn new(trade: &Trade) -> Self {
+        Self {
+            outcome: trade.outcome.clone(),
+            last: trade.price,
+            open: trade.price,
+            high: trade.price,
+            low: trade.price,
+            trades: 1,
+            last_trade_at: trade.timestamp,
+            notional: trade.notional(),
+            shares: trade.size,
+        }
+    }
+
+    fn update(&mut self, trade: &Trade) {
+        self.last = trade.price;
+        self.high = self.high.max(trade.price);
+        self.low = self.low.min(trade.price);
+        self.trades += 1;
+        self.last_trade_at = self.last_trade_at.max(trade.timestamp);
+        self.notional += trade.notional();
+        self.shares += trade.size;
+    }
+
+    pub fn vwap(&self) -> f64 {
+        if self.shares > 0.0 { self.notional / self.shares } else { self.last }
+    }
+
+    /// Change in price since the first trade seen this session.
+    pub fn change(&self) -> f64 {
+        self.last - self.open
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct MarketPrices {
+    pub outcomes: Vec<OutcomeStats>,
+    /// `(unix minute, volume)` buckets, oldest first.
+    #[serde(skip)]
+    volume_by_minute: VecDeque<(u64, f64)>,
+}
+
+impl MarketPrices {
+    pub fn record(&mut self, trade: &Trade) {
+        match self.outcomes.iter_mut().find(|o| o.outcome == trade.outcome) {
+            Some(stats) => stats.update(trade),
+            None => self.outcomes.push(OutcomeStats::new(trade)),
+        }
+
+        let minute = trade.timestamp / 60;
+        match self.volume_by_minute.iter_mut().rev().find(|(m, _)| *m == minute) {
+            Some((_, volume)) => *volume += trade.size,
+            None => {
+                let at = self.volume_by_minute.partition_point(|(m, _)| *m < minute);
+                self.volume_by_minute.insert(at, (minute, trade.size));
+            }
+        }
+        while self.volume_by_minute.len() > ACTIVITY_MINUTES {
+            self.volume_by_minute.pop_front();
+        }
+    }
+
+    /// Volume for each of the last `ACTIVITY_MINUTES` minutes up to `now`, oldest first.
+    pub fn activity(&self, now: u64) -> Vec<f64> {
+        let current = now / 60;
+        let first = current.saturating_sub(ACTIVITY_MINUTES as u64 - 1);
+        (first..=current)
+            .map(|minute| {
+                self.volume_by_minute
+                    .iter()
+                    .find(|(m, _)| *m == minute)
+                    .map(|(_, v)| *v)
+                    .unwrap_or(0.0)
+            })
+            .collect()
+    }
+
+    pub fn last_trade_at(&self) -> u64 {
+        self.outcomes.iter().map(|o| o.last_trade_at).max().unwrap_or(0)
+    }
+
+    /// The outcome to headline in lists: "Yes" when the market has one, otherwise the
+    /// most traded outcome.
+    pub fn primary(&self) -> Option<&OutcomeStats> {
+        self.outcomes
+            .iter()
+            .find(|o| o.outcome.eq_ignore_ascii_case("yes"))
+            .or_else(|| self.outcomes.iter().max_by_key(|o| o.trades))
+    }
+}
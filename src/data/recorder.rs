@@ -0,0 +1,69 @@
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use flate2::Compression;
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use serde::{Deserialize, Serialize};
+
+/// How often buffered messages are flushed, so a session cut short still replays.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// One raw websocket message as stored in a recording, one JSON object per line.
+#[derive(Serialize, Deserialize)]
+pub struct RecordedMessage {
+    /// Unix milliseconds when the message arrived.
+    pub at: u64,
+    pub message: String,
+}
+
+/// Writes every raw feed message to a gzip-compressed JSON lines file.
+pub struct Recorder {
+    writer: GzEncoder<BufWriter<File>>,
+    last_flush: Instant,
+}
+
+impl Recorder {
+    /// Creates `dir` if needed and starts `pmterm-<date>-<time>.jsonl.gz` inside it.
+    pub fn create(dir: &Path) -> io::Result<(Self, PathBuf)> {
+        fs::create_dir_all(dir)?;
+        let name = chrono::Local::now().format("pmterm-%Y%m%d-%H%M%S.jsonl.gz").to_string();
+        let path = dir.join(name);
+        let file = File::create(&path)?;
+        let recorder = Self {
+            writer: GzEncoder::new(BufWriter::new(file), Compression::default()),
+            last_flush: Instant::now(),
+        };
+        Ok((recorder, path))
+    }
+
+    pub fn record(&mut self, message: &str) -> io::Result<()> {
+        let at = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64;
+        let line = RecordedMessage { at, message: message.to_string() };
+        serde_json::to_writer(&mut self.writer, &line)?;
+        self.writer.write_all(b"\n")?;
+        if self.last_flush.elapsed() >= FLUSH_INTERVAL {
+            self.writer.flush()?;
+            self.last_flush = Instant::now();
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        let _ = self.writer.try_finish();
+    }
+}
+
+/// Reads a recording line by line. A file whose writer was killed mid-stream ends
+/// at the last complete flush instead of failing.
+pub fn read_recording(path: &Path) -> io::Result<impl Iterator<Item = RecordedMessage> + Send + use<>> {
+    let reader = BufReader::new(MultiGzDecoder::new(File::open(path)?));
+    Ok(reader
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str(&line).ok()))
+}
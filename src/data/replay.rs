@@ -0,0 +1,76 @@
+use std::path::Path;
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+use crate::data::pipeline::EventSender;
+use crate::data::recorder::read_recording;
+use crate::data::ws::process_message;
+
+/// Messages read ahead of playback.
+const READ_AHEAD: usize = 1024;
+
+/// Feeds a recording back through `ws::process_message`, keeping the original gaps
+/// between messages divided by `speed`. A speed of 0 replays as fast as possible.
+pub async fn run(events: EventSender, path: &Path, speed: f64) -> anyhow::Result<()> {
+    let messages = read_recording(path)?;
+    let (tx, mut rx) = mpsc::channel(READ_AHEAD);
+    tokio::task::spawn_blocking(move || {
+        for message in messages {
+            if tx.blocking_send(message).is_err() {
+                break;
+            }
+        }
+    });
+
+    // Scheduled against the first message rather than the previous one so slow
+    // processing does not add up over a long session.
+    let mut start: Option<(Instant, u64)> = None;
+    while let Some(recorded) = rx.recv().await {
+        let (started, first_at) = *start.get_or_insert((Instant::now(), recorded.at));
+        if speed > 0.0 {
+            let offset = recorded.at.saturating_sub(first_at) as f64 / speed;
+            tokio::time::sleep_until(started + Duration::from_millis(offset as u64)).await;
+        }
+        process_message(&events, &recorded.message, true).await;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::*;
+    use crate::data::pipeline;
+    use crate::data::recorder::Recorder;
+    use crate::data::state::TimeWindow;
+
+    const TRADE: &str = r#"{"topic":"activity","type":"trades","payload":{"conditionId":"0x1","title":"Rain?","price":0.5,"size":10,"side":"BUY","outcome":"Yes","proxyWallet":"0xabc","slug":"rain","timestamp":1}}"#;
+
+    #[tokio::test]
+    async fn replays_what_was_recorded() {
+        let dir = env::temp_dir().join(format!("pmterm-replay-test-{}", process::id()));
+        let (mut recorder, path) = Recorder::create(&dir).unwrap();
+        recorder.record(TRADE).unwrap();
+        recorder.record("not json").unwrap();
+        recorder.record(&TRADE.replace("0xabc", "0xdef")).unwrap();
+        drop(recorder);
+
+        let recorded: Vec<String> = read_recording(&path).unwrap().map(|m| m.message).collect();
+        assert_eq!(recorded, vec![TRADE.to_string(), "not json".to_string(), TRADE.replace("0xabc", "0xdef")]);
+
+        let (events, mut state) = pipeline::spawn(None);
+        run(events.clone(), &path, 0.0).await.unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let replayed = state.wait_for(|s| s.window(TimeWindow::Session).total_trades == 2);
+        let snapshot = tokio::time::timeout(Duration::from_secs(5), replayed).await.unwrap().unwrap().clone();
+        // Replayed trades are stamped with the replay time, so they land in every window.
+        let window = snapshot.window(TimeWindow::FiveMinutes);
+        assert_eq!((window.total_trades, window.total_volume), (2, 20.0));
+        assert_eq!(window.top_markets[0].identifier, "rain");
+        assert_eq!(window.top_traders.len(), 2);
+    }
+}
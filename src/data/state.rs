@@ -1,77 +1,543 @@
 use std::sync::{Arc};
-use tokio::sync::Mutex;
-use std::collections::HashMap;
+use tokio::sync::watch;
+use std::collections::{HashMap, VecDeque};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-pub type SharedState = Arc<Mutex<AppState>>;
+use serde::Serialize;
 
-#[derive(Clone)]
+use crate::config::{self, Config};
+use crate::data::closing_markets::ClosingMarket;
+use crate::data::feed::Comment;
+use crate::data::leaderboard::{Leaderboard, Ranked};
+use crate::data::market_meta::MarketMeta;
+use crate::data::new_markets::NewMarket;
+use crate::data::prices::{MarketPrices, OutcomeStats};
+use crate::data::types::Payload;
+use crate::data::view::{ListSort, ListView, SortOrder, ViewSnapshot};
+
+/// Read side of the state pipeline: always holds the latest published `Snapshot`.
+pub type SharedState = watch::Receiver<Arc<Snapshot>>;
+
+#[derive(Clone, Serialize)]
 pub struct MarketData {
     pub name: String,
     pub volume: f64,
     pub identifier: String,
+    pub trades: u64,
+    pub price: Option<OutcomeStats>,
+    /// Per-minute volume over the last few minutes, oldest first.
+    pub activity: Vec<f64>,
+    pub last_trade_at: u64,
+    /// Tags and end date, once they have been looked up.
+    pub meta: Option<MarketMeta>,
+}
+
+#[derive(Clone, Serialize)]
+pub struct TraderData {
+    pub address: String,
+    pub volume: f64,
+    pub trades: u64,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TimeWindow {
+    FiveMinutes,
+    OneHour,
+    OneDay,
+    Session,
+}
+
+impl TimeWindow {
+    pub const ALL: [TimeWindow; 4] = [
+        TimeWindow::FiveMinutes,
+        TimeWindow::OneHour,
+        TimeWindow::OneDay,
+        TimeWindow::Session,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TimeWindow::FiveMinutes => "5m",
+            TimeWindow::OneHour => "1h",
+            TimeWindow::OneDay => "24h",
+            TimeWindow::Session => "session",
+        }
+    }
+
+    /// Inverse of `label`.
+    pub fn from_label(label: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|w| w.label() == label)
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            TimeWindow::FiveMinutes => TimeWindow::OneHour,
+            TimeWindow::OneHour => TimeWindow::OneDay,
+            TimeWindow::OneDay => TimeWindow::Session,
+            TimeWindow::Session => TimeWindow::FiveMinutes,
+        }
+    }
+
+    /// Length of the window in seconds, `None` for the whole session.
+    pub fn seconds(&self) -> Option<u64> {
+        match self {
+            TimeWindow::FiveMinutes => Some(5 * 60),
+            TimeWindow::OneHour => Some(60 * 60),
+            TimeWindow::OneDay => Some(24 * 60 * 60),
+            TimeWindow::Session => None,
+        }
+    }
+}
+
+pub fn now_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
+}
+
+#[derive(Clone, Serialize)]
+pub struct Trade {
+    pub timestamp: u64,
+    pub title: String,
+    pub slug: String,
+    pub condition_id: String,
+    pub side: String,
+    pub outcome: String,
+    pub price: f64,
+    pub size: f64,
+    pub trader: String,
+    pub trader_name: Option<String>,
+}
+
+impl Trade {
+    pub fn notional(&self) -> f64 {
+        self.price * self.size
+    }
+
+    pub fn is_buy(&self) -> bool {
+        self.side.eq_ignore_ascii_case("buy")
+    }
+}
+
+impl From<Payload> for Trade {
+    fn from(payload: Payload) -> Self {
+        let timestamp = if payload.timestamp > 0 { payload.timestamp } else { now_secs() };
+        Self {
+            timestamp,
+            title: payload.title,
+            slug: payload.slug,
+            condition_id: payload.condition_id,
+            side: payload.side,
+            outcome: payload.outcome,
+            price: payload.price,
+            size: payload.size,
+            trader: payload.proxy_wallet,
+            trader_name: payload.name.or(payload.pseudonym).filter(|n| !n.is_empty()),
+        }
+    }
+}
+
+/// Running totals for one `TimeWindow`, updated as trades arrive and expire.
+struct WindowAggregate {
+    window: TimeWindow,
+    markets: Leaderboard,
+    traders: Leaderboard,
+    trades: u64,
+    volume: f64,
+    /// How many trades at the front of the log have already been taken out of this window.
+    expired: usize,
+}
+
+impl WindowAggregate {
+    fn new(window: TimeWindow, config: &Config) -> Self {
+        Self {
+            window,
+            markets: Leaderboard::new(config.max_markets),
+            traders: Leaderboard::new(config.max_traders),
+            trades: 0,
+            volume: 0.0,
+            expired: 0,
+        }
+    }
+
+    /// Counts trade number `seq`, returning the slug of any market evicted to stay
+    /// under the cap.
+    fn add(&mut self, trade: &Trade, seq: u64) -> Option<String> {
+        let evicted = self.markets.add(&trade.title, trade.size, &trade.slug, seq);
+        self.traders.add(&trade.trader, trade.size, "", seq);
+        self.trades += 1;
+        self.volume += trade.size;
+        evicted
+    }
+
+    fn remove(&mut self, trade: &Trade, seq: u64) {
+        self.markets.subtract(&trade.title, trade.size, seq);
+        self.traders.subtract(&trade.trader, trade.size, seq);
+        self.trades = self.trades.saturating_sub(1);
+        self.volume = (self.volume - trade.size).max(0.0);
+    }
 }
+
 pub struct AppState {
     pub traders: Vec<(String, f64)>,
-    pub top_markets: HashMap<String, MarketData>,
-    pub closing_markets: Vec<String>,
+    pub closing_markets: Arc<Vec<ClosingMarket>>,
     pub tracked_markets: u64,
-    pub tracked_traders: HashMap<String, f64>,
     pub tick: u64,
-    pub new_markets: Vec<(String, String)>,
+    pub new_markets: Arc<Vec<NewMarket>>,
     pub markets_updated: u64,
+    pub tape: VecDeque<Trade>,
+    pub tape_threshold: f64,
+    pub trade_log: VecDeque<Trade>,
+    /// Trades ever added to `trade_log`; the front of the log is trade number
+    /// `logged - trade_log.len()`.
+    logged: u64,
+    /// Sequence numbers of each market's trades in `trade_log`, oldest first.
+    market_log: HashMap<String, VecDeque<u64>>,
+    max_log_trades: usize,
+    windows: Vec<WindowAggregate>,
+    /// Live prices per market slug, kept for the markets in the session leaderboard.
+    prices: HashMap<String, MarketPrices>,
+    /// Tags and end dates per market slug, kept for the same markets as `prices`.
+    market_meta: HashMap<String, MarketMeta>,
+    /// Comments from the live feed across all events, oldest first.
+    comments: VecDeque<Comment>,
+    /// Ids of comments the feed reported as removed, oldest first.
+    removed_comments: VecDeque<String>,
+    /// Filter and sort of the dashboard's top lists, applied when building snapshots.
+    view: ListView,
     started_at: SystemTime,
 }
 const DISPLAY_COUNT: usize = 50;
+const TAPE_CAPACITY: usize = 200;
+const COMMENT_CAPACITY: usize = 500;
+
+/// Live comment activity for one event, merged by the market page into the fetched list.
+#[derive(Clone, Default)]
+pub struct LiveComments {
+    /// Newest first.
+    pub comments: Vec<Comment>,
+    pub removed: Vec<String>,
+}
 
 impl AppState {
     pub fn new() -> Self {
+        let config = config::get();
         Self {
-            new_markets: vec!(),
+            new_markets: Arc::new(vec!()),
             traders: vec!(),
-            top_markets: HashMap::new(),
-            closing_markets: vec!(),
-            tracked_traders: HashMap::new(),
+            closing_markets: Arc::new(vec!()),
             tracked_markets: 0,
             tick: 0,
             markets_updated: 0,
+            tape: VecDeque::with_capacity(TAPE_CAPACITY),
+            tape_threshold: config.tape_threshold,
+            trade_log: VecDeque::new(),
+            logged: 0,
+            market_log: HashMap::new(),
+            max_log_trades: config.max_log_trades,
+            windows: TimeWindow::ALL.iter().map(|w| WindowAggregate::new(*w, config)).collect(),
+            prices: HashMap::new(),
+            market_meta: HashMap::new(),
+            comments: VecDeque::new(),
+            removed_comments: VecDeque::new(),
+            view: ListView::default(),
             started_at: SystemTime::now(),
 
         }
     }
 
-    pub fn add_trade(&mut self, title: String, trade_size: f64, trader_address: String, id: String ) {
+    pub fn add_trade(&mut self, trade: Trade) {
         self.tick += 1;
         self.tracked_markets += 1;
-        self.tracked_traders.entry(trader_address).and_modify(|v| *v += trade_size).or_insert(trade_size);
-        self.top_markets.entry(title.clone()).and_modify(|v| v.volume += trade_size).or_insert(MarketData {name: title,volume: trade_size, identifier: id });
+
+        let seq = self.logged;
+        for aggregate in self.windows.iter_mut() {
+            let evicted = aggregate.add(&trade, seq);
+            if aggregate.window == TimeWindow::Session
+                && let Some(slug) = evicted
+            {
+                self.prices.remove(&slug);
+                self.market_meta.remove(&slug);
+            }
+        }
+        self.prices.entry(trade.slug.clone()).or_default().record(&trade);
+
+        if trade.notional() >= self.tape_threshold {
+            self.tape.push_front(trade.clone());
+            self.tape.truncate(TAPE_CAPACITY);
+        }
+
+        self.market_log.entry(trade.slug.clone()).or_default().push_back(seq);
+        self.trade_log.push_back(trade);
+        self.logged += 1;
+        self.evict_expired(now_secs());
     }
 
-    pub fn get_top_markets(&mut self) -> (Vec<MarketData>, f64) {
-        let mut top_markets_vals = self.top_markets.values().cloned().collect::<Vec<_>>();
-        top_markets_vals.sort_by(|a, b| b.volume.partial_cmp(&a.volume).unwrap());
-        (top_markets_vals.into_iter().take(DISPLAY_COUNT).collect(), self.tracked_markets as f64)
+    /// Takes trades that have aged out of each window back out of its totals, then
+    /// drops trades from the log once they are older than the longest window or the
+    /// log is over its cap. Returns whether any totals changed.
+    pub fn evict_expired(&mut self, now: u64) -> bool {
+        let mut changed = false;
+        let overflow = self.trade_log.len().saturating_sub(self.max_log_trades);
+        let first_seq = self.logged - self.trade_log.len() as u64;
+        for aggregate in self.windows.iter_mut() {
+            let Some(secs) = aggregate.window.seconds() else {
+                continue;
+            };
+            let cutoff = now.saturating_sub(secs);
+            while let Some(trade) = self.trade_log.get(aggregate.expired)
+                && (aggregate.expired < overflow || trade.timestamp < cutoff)
+            {
+                aggregate.remove(trade, first_seq + aggregate.expired as u64);
+                aggregate.expired += 1;
+                changed = true;
+            }
+        }
+
+        let drop = self
+            .windows
+            .iter()
+            .filter(|a| a.window.seconds().is_some())
+            .map(|a| a.expired)
+            .min()
+            .unwrap_or(0);
+        for trade in self.trade_log.drain(..drop) {
+            if let Some(seqs) = self.market_log.get_mut(&trade.slug) {
+                seqs.pop_front();
+                if seqs.is_empty() {
+                    self.market_log.remove(&trade.slug);
+                }
+            }
+        }
+        for aggregate in self.windows.iter_mut() {
+            aggregate.expired = aggregate.expired.saturating_sub(drop);
+        }
+        changed
     }
 
-    pub fn get_top_traders(&mut self) -> Vec<(String, f64)> {
-        let mut traders_vec = self.tracked_traders.iter().map(|(k, v)| (k.clone(), *v)).collect::<Vec<_>>();
-        traders_vec.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
-        traders_vec.into_iter().take(DISPLAY_COUNT).collect()
+    fn aggregate(&self, window: TimeWindow) -> &WindowAggregate {
+        self.windows.iter().find(|a| a.window == window).expect("every window has an aggregate")
     }
 
-    pub fn set_new_markets(&mut self, markets: Vec<(String, String)>) {
-        self.new_markets = markets;
-        self.markets_updated = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    pub fn tape(&self) -> Vec<Trade> {
+        self.tape.iter().cloned().collect()
     }
 
-    pub fn general_stats(&self) -> (usize, u64, u64, f64) {
-        let time_running = self.started_at.elapsed().unwrap().as_secs();
-        let total_trades =self.tick;
-        let total_markets = self.top_markets.len();
-        let mut total_volume: f64  = 0.0;
-        for x in self.top_markets.values() {
-            total_volume += x.volume;
+    /// Markets and traders held by each window's leaderboards.
+    pub fn window_sizes(&self) -> impl Iterator<Item = (TimeWindow, usize, usize)> + '_ {
+        self.windows.iter().map(|a| (a.window, a.markets.len(), a.traders.len()))
+    }
+
+    /// Markets with live price statistics.
+    pub fn priced_markets(&self) -> usize {
+        self.prices.len()
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        let windows = TimeWindow::ALL
+            .iter()
+            .map(|w| {
+                let (total_markets, _, total_trades, total_volume) = self.general_stats(*w);
+                let window = WindowSnapshot {
+                    top_markets: self.get_top_markets(*w).0,
+                    top_traders: self.get_top_traders(*w),
+                    total_markets,
+                    total_trades,
+                    total_volume,
+                };
+                (*w, window)
+            })
+            .collect();
+
+        Snapshot {
+            windows,
+            tape: self.tape(),
+            tape_threshold: self.tape_threshold,
+            new_markets: self.new_markets.clone(),
+            closing_markets: self.closing_markets.clone(),
+            view: self.view_snapshot(),
+            markets_updated: self.markets_updated,
+            started_at: self.started_at,
         }
+    }
+
+    pub fn set_view(&mut self, view: ListView) {
+        self.view = view;
+    }
+
+    /// Applies the view to every market and trader in its window, not just the top by
+    /// volume, then keeps the first `DISPLAY_COUNT`.
+    fn view_snapshot(&self) -> ViewSnapshot {
+        let view = &self.view;
+        let aggregate = self.aggregate(view.window);
+        let now = now_secs();
+
+        let matching = aggregate.markets.top(usize::MAX).filter(|entry| {
+            let meta = self.market_meta.get(entry.identifier);
+            view.filter.matches_market(entry.key, entry.volume, meta, now)
+        });
+        // The leaderboard is already ordered by volume, so that order needs no sort.
+        let mut markets: Vec<MarketData> = if view.market_sort == SortOrder::new(ListSort::Volume) {
+            matching.take(DISPLAY_COUNT).map(|entry| self.market_data(&entry)).collect()
+        } else {
+            let mut markets: Vec<MarketData> = matching.map(|entry| self.market_data(&entry)).collect();
+            view.market_sort.key.sort_markets(&mut markets, view.market_sort.descending);
+            markets.truncate(DISPLAY_COUNT);
+            markets
+        };
+        self.fill_activity(&mut markets, now);
+
+        let matching = aggregate
+            .traders
+            .top(usize::MAX)
+            .filter(|entry| view.filter.matches_trader(entry.key, entry.volume));
+        let traders = if view.trader_sort == SortOrder::new(ListSort::Volume) {
+            matching.take(DISPLAY_COUNT).map(|entry| trader_data(&entry)).collect()
+        } else {
+            let mut traders: Vec<TraderData> = matching.map(|entry| trader_data(&entry)).collect();
+            view.trader_sort.key.sort_traders(&mut traders, view.trader_sort.descending);
+            traders.truncate(DISPLAY_COUNT);
+            traders
+        };
+
+        ViewSnapshot { markets, traders }
+    }
+
+    pub fn add_comment(&mut self, comment: Comment) {
+        if self.comments.len() == COMMENT_CAPACITY {
+            self.comments.pop_front();
+        }
+        self.comments.push_back(comment);
+    }
+
+    pub fn remove_comment(&mut self, id: String) {
+        self.comments.retain(|c| c.id != id);
+        if self.removed_comments.len() == COMMENT_CAPACITY {
+            self.removed_comments.pop_front();
+        }
+        self.removed_comments.push_back(id);
+    }
+
+    /// Adjusts the reaction count of a live comment. Reactions to older comments are
+    /// picked up when the market page refetches them.
+    pub fn react(&mut self, comment_id: u64, added: bool) {
+        let id = comment_id.to_string();
+        if let Some(comment) = self.comments.iter_mut().find(|c| c.id == id) {
+            comment.reaction_count = if added {
+                comment.reaction_count + 1
+            } else {
+                comment.reaction_count.saturating_sub(1)
+            };
+        }
+    }
+
+    pub fn event_comments(&self, event_id: u64) -> LiveComments {
+        LiveComments {
+            comments: self
+                .comments
+                .iter()
+                .rev()
+                .filter(|c| c.parent_entity_type == "Event" && c.parent_entity_id == event_id)
+                .cloned()
+                .collect(),
+            removed: self.removed_comments.iter().cloned().collect(),
+        }
+    }
+
+    /// Trades for one market still in the rolling log, oldest first.
+    pub fn market_trades(&self, slug: &str) -> Vec<Trade> {
+        let first_seq = self.logged - self.trade_log.len() as u64;
+        self.market_log
+            .get(slug)
+            .into_iter()
+            .flatten()
+            .filter_map(|seq| self.trade_log.get((seq - first_seq) as usize))
+            .cloned()
+            .collect()
+    }
+
+    pub fn market_prices(&self, slug: &str) -> Option<MarketPrices> {
+        self.prices.get(slug).cloned()
+    }
+
+    pub fn trader_volume(&self, address: &str) -> f64 {
+        self.aggregate(TimeWindow::Session).traders.get(address).unwrap_or(0.0)
+    }
+
+    pub fn get_top_markets(&self, window: TimeWindow) -> (Vec<MarketData>, f64) {
+        let mut top_markets_vals: Vec<MarketData> = self
+            .aggregate(window)
+            .markets
+            .top(DISPLAY_COUNT)
+            .map(|entry| self.market_data(&entry))
+            .collect();
+        self.fill_activity(&mut top_markets_vals, now_secs());
+        (top_markets_vals, self.tracked_markets as f64)
+    }
+
+    pub fn get_top_traders(&self, window: TimeWindow) -> Vec<TraderData> {
+        self.aggregate(window)
+            .traders
+            .top(DISPLAY_COUNT)
+            .map(|entry| trader_data(&entry))
+            .collect()
+    }
+
+    /// A leaderboard entry with its prices and meta. The activity sparkline is left
+    /// empty for `fill_activity`, so it is only built for the markets shown.
+    fn market_data(&self, entry: &Ranked) -> MarketData {
+        let prices = self.prices.get(entry.identifier);
+        MarketData {
+            name: entry.key.to_string(),
+            volume: entry.volume,
+            identifier: entry.identifier.to_string(),
+            trades: entry.count,
+            price: prices.and_then(|p| p.primary().cloned()),
+            activity: vec![],
+            last_trade_at: prices.map(|p| p.last_trade_at()).unwrap_or(0),
+            meta: self.market_meta.get(entry.identifier).cloned(),
+        }
+    }
+
+    fn fill_activity(&self, markets: &mut [MarketData], now: u64) {
+        for market in markets {
+            if let Some(prices) = self.prices.get(&market.identifier) {
+                market.activity = prices.activity(now);
+            }
+        }
+    }
+
+    pub fn set_new_markets(&mut self, markets: Vec<NewMarket>) {
+        let tracked: Vec<(String, MarketMeta)> = markets
+            .iter()
+            .filter(|m| self.prices.contains_key(&m.slug))
+            .map(|m| (m.slug.clone(), m.meta.clone()))
+            .collect();
+        self.set_market_meta(tracked);
+        self.new_markets = Arc::new(markets);
+        self.markets_updated = now_secs();
+    }
+
+    pub fn set_closing_markets(&mut self, markets: Vec<ClosingMarket>) {
+        self.closing_markets = Arc::new(markets);
+    }
+
+    /// Stores looked up tags and end dates, ignoring markets that are no longer tracked.
+    pub fn set_market_meta(&mut self, meta: Vec<(String, MarketMeta)>) {
+        for (slug, meta) in meta {
+            if self.prices.contains_key(&slug) {
+                self.market_meta.insert(slug, meta);
+            }
+        }
+    }
+
+    pub fn general_stats(&self, window: TimeWindow) -> (usize, u64, u64, f64) {
+        let time_running = self.started_at.elapsed().unwrap().as_secs();
+        let aggregate = self.aggregate(window);
+        let total_markets = match window {
+            TimeWindow::Session => aggregate.markets.inserted() as usize,
+            _ => aggregate.markets.len(),
+        };
+        let total_trades = aggregate.trades;
+        let total_volume = aggregate.volume;
         (
             total_markets,
             time_running,
@@ -81,26 +547,166 @@ impl AppState {
     }
 
 
+    pub fn increment_market_count(&mut self) {
+        self.tracked_markets += 1;
+    }
+}
+
+fn trader_data(entry: &Ranked) -> TraderData {
+    TraderData {
+        address: entry.key.to_string(),
+        volume: entry.volume,
+        trades: entry.count,
+    }
+}
+
+impl Default for AppState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct WindowSnapshot {
+    pub top_markets: Vec<MarketData>,
+    pub top_traders: Vec<TraderData>,
+    pub total_markets: usize,
+    pub total_trades: u64,
+    pub total_volume: f64,
+}
+
+/// Immutable copy of the aggregates published by the state actor for the UI to read.
+pub struct Snapshot {
+    pub windows: Vec<(TimeWindow, WindowSnapshot)>,
+    pub tape: Vec<Trade>,
+    pub tape_threshold: f64,
+    pub new_markets: Arc<Vec<NewMarket>>,
+    /// Open markets ending within the closing horizon, soonest first.
+    pub closing_markets: Arc<Vec<ClosingMarket>>,
+    /// Top markets and traders for the dashboard's current filter and sort.
+    pub view: ViewSnapshot,
+    pub markets_updated: u64,
+    pub started_at: SystemTime,
+}
+
+impl Snapshot {
+    pub fn window(&self, window: TimeWindow) -> WindowSnapshot {
+        self.windows
+            .iter()
+            .find(|(w, _)| *w == window)
+            .map(|(_, data)| data.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn time_running(&self) -> u64 {
+        self.started_at.elapsed().map(|d| d.as_secs()).unwrap_or(0)
+    }
+
     pub fn last_updated_markets(&self) -> String {
-        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        let now = now_secs();
         if self.markets_updated == 0 {
             return String::from("never");
         }
         let diff = now.saturating_sub(self.markets_updated);
         format!("{} secs", diff)
     }
+}
 
-    pub fn new_markets(&self) -> &Vec<(String, String)> {
-         &self.new_markets
+impl Default for Snapshot {
+    fn default() -> Self {
+        AppState::new().snapshot()
     }
+}
 
-    pub fn increment_market_count(&mut self) {
-        self.tracked_markets += 1;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(slug: &str, trader: &str, size: f64, timestamp: u64) -> Trade {
+        Trade {
+            timestamp,
+            title: slug.to_string(),
+            slug: slug.to_string(),
+            condition_id: String::new(),
+            side: "BUY".to_string(),
+            outcome: "Yes".to_string(),
+            price: 0.5,
+            size,
+            trader: trader.to_string(),
+            trader_name: None,
+        }
     }
-}
 
-impl Default for AppState {
-    fn default() -> Self {
-        Self::new()
+    #[test]
+    fn evict_expired_takes_old_trades_out_of_each_window() {
+        let now = now_secs();
+        let mut state = AppState::new();
+        state.add_trade(trade("rain", "0xa", 10.0, now - 30 * 60));
+        state.add_trade(trade("snow", "0xb", 5.0, now));
+
+        // Adding already expires the half hour old trade from the five minute window.
+        let five = state.aggregate(TimeWindow::FiveMinutes);
+        assert_eq!((five.trades, five.markets.get("rain")), (1, None));
+        assert!(!state.evict_expired(now));
+
+        assert!(state.evict_expired(now + 10 * 60));
+        let five = state.aggregate(TimeWindow::FiveMinutes);
+        assert_eq!((five.trades, five.volume, five.markets.len()), (0, 0.0, 0));
+        let hour = state.aggregate(TimeWindow::OneHour);
+        assert_eq!((hour.trades, hour.markets.get("rain")), (2, Some(10.0)));
+        assert_eq!(state.market_trades("rain").len(), 1);
+
+        assert!(state.evict_expired(now + 2 * 24 * 60 * 60));
+        for window in [TimeWindow::FiveMinutes, TimeWindow::OneHour, TimeWindow::OneDay] {
+            let aggregate = state.aggregate(window);
+            assert_eq!((aggregate.trades, aggregate.markets.len(), aggregate.traders.len()), (0, 0, 0));
+        }
+        assert!(state.trade_log.is_empty());
+        assert!(state.market_trades("rain").is_empty());
+
+        let session = state.aggregate(TimeWindow::Session);
+        assert_eq!((session.trades, session.volume), (2, 15.0));
+        assert_eq!(session.traders.get("0xb"), Some(5.0));
+    }
+
+    #[test]
+    fn log_cap_expires_the_oldest_trades() {
+        let now = now_secs();
+        let mut state = AppState::new();
+        state.max_log_trades = 2;
+        state.add_trade(trade("rain", "0xa", 1.0, now));
+        state.add_trade(trade("snow", "0xa", 2.0, now));
+        state.add_trade(trade("snow", "0xb", 3.0, now));
+
+        assert_eq!(state.trade_log.len(), 2);
+        assert!(state.market_trades("rain").is_empty());
+        assert_eq!(state.market_trades("snow").len(), 2);
+        let five = state.aggregate(TimeWindow::FiveMinutes);
+        assert_eq!((five.trades, five.markets.get("rain"), five.traders.get("0xa")), (2, None, Some(2.0)));
+        assert_eq!(state.aggregate(TimeWindow::Session).trades, 3);
+    }
+
+    #[test]
+    fn view_filters_beyond_the_displayed_top() {
+        let now = now_secs();
+        let mut state = AppState::new();
+        for i in 0..DISPLAY_COUNT + 10 {
+            state.add_trade(trade(&format!("market-{}", i), "0xa", 100.0 + i as f64, now));
+        }
+        state.add_trade(trade("needle", "0xa", 1.0, now));
+        assert_eq!(state.view_snapshot().markets.len(), DISPLAY_COUNT);
+
+        let mut view = ListView::default();
+        view.filter.text = Some("needle".to_string());
+        state.set_view(view);
+        let markets = state.view_snapshot().markets;
+        assert_eq!(markets.iter().map(|m| m.name.as_str()).collect::<Vec<_>>(), vec!["needle"]);
+
+        let mut view = ListView::default();
+        view.market_sort.descending = false;
+        state.set_view(view);
+        let markets = state.view_snapshot().markets;
+        assert_eq!(markets.len(), DISPLAY_COUNT);
+        assert_eq!(markets[0].name, "needle");
     }
 }
\ No newline at end of file
@@ -0,0 +1,166 @@
+use std::path::Path;
+use std::sync::mpsc;
+use std::thread;
+
+use rusqlite::{Connection, params};
+
+use crate::config;
+use crate::data::state::{now_secs, Trade};
+
+/// Every trade seen on the live feed, kept in a local SQLite file across sessions.
+pub struct TradeDb {
+    conn: Connection,
+}
+
+impl TradeDb {
+    pub fn open(path: &Path) -> rusqlite::Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             CREATE TABLE IF NOT EXISTS trades (
+                 id INTEGER PRIMARY KEY,
+                 timestamp INTEGER NOT NULL,
+                 title TEXT NOT NULL,
+                 slug TEXT NOT NULL,
+                 condition_id TEXT NOT NULL,
+                 outcome TEXT NOT NULL,
+                 side TEXT NOT NULL,
+                 price REAL NOT NULL,
+                 size REAL NOT NULL,
+                 wallet TEXT NOT NULL,
+                 trader_name TEXT
+             );
+             CREATE INDEX IF NOT EXISTS trades_timestamp ON trades (timestamp);",
+        )?;
+        Ok(Self { conn })
+    }
+
+    pub fn insert(&mut self, trades: &[Trade]) -> rusqlite::Result<()> {
+        let tx = self.conn.transaction()?;
+        {
+            let mut insert = tx.prepare_cached(
+                "INSERT INTO trades (timestamp, title, slug, condition_id, outcome, side, price, size, wallet, trader_name)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            )?;
+            for t in trades {
+                insert.execute(params![
+                    t.timestamp as i64,
+                    t.title,
+                    t.slug,
+                    t.condition_id,
+                    t.outcome,
+                    t.side,
+                    t.price,
+                    t.size,
+                    t.trader,
+                    t.trader_name,
+                ])?;
+            }
+        }
+        tx.commit()
+    }
+
+    /// Trades at or after `since` (unix seconds), oldest first.
+    pub fn load_since(&self, since: u64) -> rusqlite::Result<Vec<Trade>> {
+        let mut query = self.conn.prepare(
+            "SELECT timestamp, title, slug, condition_id, outcome, side, price, size, wallet, trader_name
+             FROM trades WHERE timestamp >= ?1 ORDER BY timestamp, id",
+        )?;
+        let rows = query.query_map([since as i64], |row| {
+            Ok(Trade {
+                timestamp: row.get::<_, i64>(0)? as u64,
+                title: row.get(1)?,
+                slug: row.get(2)?,
+                condition_id: row.get(3)?,
+                outcome: row.get(4)?,
+                side: row.get(5)?,
+                price: row.get(6)?,
+                size: row.get(7)?,
+                trader: row.get(8)?,
+                trader_name: row.get(9)?,
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// Deletes trades older than `before` (unix seconds), returning how many went.
+    pub fn prune(&self, before: u64) -> rusqlite::Result<usize> {
+        self.conn.execute("DELETE FROM trades WHERE timestamp < ?1", [before as i64])
+    }
+
+    /// Opens the database named by `trade_db` in the config and drops trades past the
+    /// retention period. `None` when saving is disabled or the file cannot be opened.
+    pub fn open_configured() -> Option<Self> {
+        let config = config::get();
+        if config.trade_db.is_empty() {
+            return None;
+        }
+        let db = match Self::open(Path::new(&config.trade_db)) {
+            Ok(db) => db,
+            Err(e) => {
+                tracing::error!("Not saving trades, cannot open {}: {}", config.trade_db, e);
+                return None;
+            }
+        };
+        let cutoff = now_secs().saturating_sub(config.trade_db_retention_days * 24 * 60 * 60);
+        if let Err(e) = db.prune(cutoff) {
+            tracing::warn!("Failed to prune old trades: {}", e);
+        }
+        Some(db)
+    }
+
+    /// Moves the database to a background thread that inserts each batch it is sent,
+    /// so the state actor never waits on disk.
+    pub fn spawn_writer(mut self) -> mpsc::Sender<Vec<Trade>> {
+        let (tx, rx) = mpsc::channel::<Vec<Trade>>();
+        thread::spawn(move || {
+            for batch in rx {
+                if let Err(e) = self.insert(&batch) {
+                    tracing::error!("Failed to save {} trades: {}", batch.len(), e);
+                }
+            }
+        });
+        tx
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(slug: &str, timestamp: u64, trader_name: Option<&str>) -> Trade {
+        Trade {
+            timestamp,
+            title: format!("{}?", slug),
+            slug: slug.to_string(),
+            condition_id: format!("0x{}", slug),
+            side: "SELL".to_string(),
+            outcome: "No".to_string(),
+            price: 0.25,
+            size: 40.0,
+            trader: "0xabc".to_string(),
+            trader_name: trader_name.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn saves_loads_and_prunes_trades() {
+        let mut db = TradeDb::open(Path::new(":memory:")).unwrap();
+        db.insert(&[trade("rain", 300, Some("alice")), trade("snow", 100, None)]).unwrap();
+        db.insert(&[trade("hail", 200, None)]).unwrap();
+
+        let all = db.load_since(0).unwrap();
+        let slugs: Vec<&str> = all.iter().map(|t| t.slug.as_str()).collect();
+        assert_eq!(slugs, ["snow", "hail", "rain"]);
+        let rain = &all[2];
+        assert_eq!((rain.timestamp, rain.title.as_str(), rain.condition_id.as_str()), (300, "rain?", "0xrain"));
+        assert_eq!((rain.side.as_str(), rain.outcome.as_str(), rain.price, rain.size), ("SELL", "No", 0.25, 40.0));
+        assert_eq!((rain.trader.as_str(), rain.trader_name.as_deref()), ("0xabc", Some("alice")));
+        assert_eq!(all[0].trader_name, None);
+
+        assert_eq!(db.load_since(200).unwrap().len(), 2);
+        assert_eq!(db.prune(200).unwrap(), 1);
+        let left: Vec<u64> = db.load_since(0).unwrap().iter().map(|t| t.timestamp).collect();
+        assert_eq!(left, [200, 300]);
+    }
+}
@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -11,13 +11,10 @@ pub struct Payload {
     pub outcome: String,
     pub proxy_wallet: String,
     pub slug: String,
-
-    
-}
-
-#[derive(Deserialize)]
-pub struct FullPayload {
-    pub payload: Payload,
+    #[serde(default)]
+    pub timestamp: u64,
+    pub name: Option<String>,
+    pub pseudonym: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -31,12 +28,21 @@ pub struct MarketData {
     pub slug: String,
     pub image: Option<String>,
     pub icon: Option<String>,
-    
+
     pub liquidity: Option<String>,
     #[serde(default)]
     pub volume: String,
+    pub created_at: Option<String>,
+    pub category: Option<String>,
+    /// Only present when requested with `include_tag=true`.
+    pub tags: Option<Vec<Tag>>,
 }
-#[derive(Deserialize, Debug)]
+
+#[derive(Deserialize)]
+pub struct Tag {
+    pub label: String,
+}
+#[derive(Deserialize, Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct MarketSpecificDetails {
     pub id: String,
@@ -57,5 +63,63 @@ pub struct MarketSpecificDetails {
     pub spread: f32,
     pub best_bid: f64,
     pub best_ask: f64,
+    /// False once the order book stops taking orders, which can be before `closed`.
+    pub accepting_orders: Option<bool>,
+    /// JSON-encoded prices in the same order as `outcome_names`; 1 and 0 once resolved.
+    pub outcome_prices: Option<String>,
+    /// UMA oracle state, such as `proposed`, `disputed` or `resolved`.
+    pub uma_resolution_status: Option<String>,
+    /// End of the current UMA challenge period, RFC 3339.
+    pub uma_end_date: Option<String>,
+    /// The event the market belongs to, which comments are attached to.
+    #[serde(default)]
+    pub events: Vec<EventRef>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EventRef {
+    pub id: String,
+}
+
+impl MarketSpecificDetails {
+    /// Outcome names, decoded from the JSON-encoded `outcomes` string.
+    pub fn outcome_names(&self) -> Vec<String> {
+        serde_json::from_str(&self.outcomes).unwrap_or_default()
+    }
+
+    /// CLOB token ids in the same order as `outcome_names`.
+    pub fn token_ids(&self) -> Vec<String> {
+        serde_json::from_str(&self.clob_token_ids).unwrap_or_default()
+    }
+
+    /// Whether buy orders can still be placed.
+    pub fn is_tradable(&self) -> bool {
+        self.active && !self.closed && self.accepting_orders != Some(false)
+    }
+
+    /// Outcome prices, decoded from the JSON-encoded `outcome_prices` string.
+    pub fn prices(&self) -> Vec<f64> {
+        let prices: Vec<String> = self
+            .outcome_prices
+            .as_deref()
+            .and_then(|p| serde_json::from_str(p).ok())
+            .unwrap_or_default();
+        prices.iter().map(|p| p.parse().unwrap_or(0.0)).collect()
+    }
+
+    /// The outcome priced at or above `threshold`, with its price. Gamma does not
+    /// expose what a UMA proposal said, so a proposed market's prices stand in for it.
+    pub fn leading_outcome(&self, threshold: f64) -> Option<(String, f64)> {
+        let names = self.outcome_names();
+        self.prices()
+            .into_iter()
+            .enumerate()
+            .find(|(_, price)| *price >= threshold)
+            .and_then(|(idx, price)| Some((names.get(idx)?.clone(), price)))
+    }
 
+    /// Id of the parent event, as comments refer to it.
+    pub fn event_id(&self) -> Option<u64> {
+        self.events.first()?.id.parse().ok()
+    }
 }
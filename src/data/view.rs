@@ -0,0 +1,185 @@
+use crate::data::closing_markets::ClosingMarket;
+use crate::data::market_meta::MarketMeta;
+use crate::data::new_markets::NewMarket;
+use crate::data::state::{MarketData, TimeWindow, TraderData};
+
+/// Parsed filter criteria applied to the dashboard lists.
+#[derive(Clone, Default, PartialEq)]
+pub struct ListFilter {
+    pub text: Option<String>,
+    pub tag: Option<String>,
+    pub min_volume: f64,
+    /// Seconds from now.
+    pub ends_within: Option<u64>,
+}
+
+impl ListFilter {
+    pub fn matches_market(&self, name: &str, volume: f64, meta: Option<&MarketMeta>, now: u64) -> bool {
+        if volume < self.min_volume {
+            return false;
+        }
+        if let Some(text) = &self.text
+            && !name.to_lowercase().contains(text)
+        {
+            return false;
+        }
+        if let Some(tag) = &self.tag {
+            let tagged = meta.is_some_and(|m| m.tags.iter().any(|t| t.to_lowercase().contains(tag)));
+            if !tagged {
+                return false;
+            }
+        }
+        if let Some(within) = self.ends_within {
+            let ends_in_window = meta
+                .and_then(|m| m.end_date)
+                .is_some_and(|end| end >= now && end - now <= within);
+            if !ends_in_window {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Traders have no tags or end date, so only the text and volume criteria apply.
+    pub fn matches_trader(&self, address: &str, volume: f64) -> bool {
+        volume >= self.min_volume
+            && self.text.as_ref().is_none_or(|text| address.to_lowercase().contains(text))
+    }
+}
+
+/// Key a dashboard list is ordered by.
+#[derive(Clone, Copy, PartialEq)]
+pub enum ListSort {
+    Volume,
+    Trades,
+    Change,
+    Recency,
+    Name,
+    /// Soonest end date first.
+    Ending,
+}
+
+/// Sort key and direction of one list.
+#[derive(Clone, Copy, PartialEq)]
+pub struct SortOrder {
+    pub key: ListSort,
+    pub descending: bool,
+}
+
+impl SortOrder {
+    pub fn new(key: ListSort) -> Self {
+        Self { key, descending: key.starts_descending() }
+    }
+
+    /// Moves to the next key the list supports. Names start A-Z, end dates soonest
+    /// first, everything else highest first.
+    pub fn next(&mut self, keys: &[ListSort]) {
+        let at = keys.iter().position(|k| *k == self.key).unwrap_or(0);
+        self.key = keys[(at + 1) % keys.len()];
+        self.descending = self.key.starts_descending();
+    }
+
+    pub fn arrow(&self) -> &'static str {
+        if self.descending { "▼" } else { "▲" }
+    }
+
+    pub fn label(&self) -> String {
+        format!("{}{}", self.key.label(), self.arrow())
+    }
+}
+
+impl ListSort {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ListSort::Volume => "volume",
+            ListSort::Trades => "trades",
+            ListSort::Change => "change",
+            ListSort::Recency => "recency",
+            ListSort::Name => "name",
+            ListSort::Ending => "end date",
+        }
+    }
+
+    fn starts_descending(&self) -> bool {
+        !matches!(self, ListSort::Name | ListSort::Ending)
+    }
+
+    pub fn sort_markets(&self, markets: &mut [MarketData], descending: bool) {
+        let change = |m: &MarketData| m.price.as_ref().map(|p| p.change()).unwrap_or(0.0);
+        markets.sort_by(|a, b| {
+            let ordering = match self {
+                ListSort::Volume => a.volume.total_cmp(&b.volume),
+                ListSort::Trades => a.trades.cmp(&b.trades),
+                ListSort::Change => change(a).total_cmp(&change(b)),
+                ListSort::Recency => a.last_trade_at.cmp(&b.last_trade_at),
+                ListSort::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+                ListSort::Ending => a.meta.as_ref().and_then(|m| m.end_date).cmp(&b.meta.as_ref().and_then(|m| m.end_date)),
+            };
+            if descending { ordering.reverse() } else { ordering }
+        });
+    }
+
+    pub fn sort_traders(&self, traders: &mut [TraderData], descending: bool) {
+        traders.sort_by(|a, b| {
+            let ordering = match self {
+                ListSort::Trades => a.trades.cmp(&b.trades),
+                ListSort::Name => a.address.cmp(&b.address),
+                ListSort::Volume | ListSort::Change | ListSort::Recency | ListSort::Ending => {
+                    a.volume.total_cmp(&b.volume)
+                }
+            };
+            if descending { ordering.reverse() } else { ordering }
+        });
+    }
+
+    pub fn sort_new_markets(&self, markets: &mut [NewMarket], descending: bool) {
+        markets.sort_by(|a, b| {
+            let ordering = match self {
+                ListSort::Recency => a.created_at.cmp(&b.created_at),
+                ListSort::Name => a.question.to_lowercase().cmp(&b.question.to_lowercase()),
+                ListSort::Ending => a.meta.end_date.cmp(&b.meta.end_date),
+                ListSort::Volume | ListSort::Trades | ListSort::Change => a.volume.total_cmp(&b.volume),
+            };
+            if descending { ordering.reverse() } else { ordering }
+        });
+    }
+
+    pub fn sort_closing_markets(&self, markets: &mut [ClosingMarket], descending: bool) {
+        markets.sort_by(|a, b| {
+            let ordering = match self {
+                ListSort::Name => a.question.to_lowercase().cmp(&b.question.to_lowercase()),
+                ListSort::Volume | ListSort::Trades | ListSort::Change => a.volume.total_cmp(&b.volume),
+                ListSort::Ending | ListSort::Recency => a.end_date.cmp(&b.end_date),
+            };
+            if descending { ordering.reverse() } else { ordering }
+        });
+    }
+}
+
+/// What the dashboard's Top Markets and Top Traders show. The state actor applies it to
+/// the full leaderboards, so markets and traders outside the top by volume can match.
+#[derive(Clone, PartialEq)]
+pub struct ListView {
+    pub window: TimeWindow,
+    pub filter: ListFilter,
+    pub market_sort: SortOrder,
+    pub trader_sort: SortOrder,
+}
+
+impl Default for ListView {
+    fn default() -> Self {
+        Self {
+            window: TimeWindow::Session,
+            filter: ListFilter::default(),
+            market_sort: SortOrder::new(ListSort::Volume),
+            trader_sort: SortOrder::new(ListSort::Volume),
+        }
+    }
+}
+
+/// The lists a `ListView` selects, as published in the snapshot.
+#[derive(Clone, Default)]
+pub struct ViewSnapshot {
+    pub markets: Vec<MarketData>,
+    pub traders: Vec<TraderData>,
+}
@@ -1,52 +1,117 @@
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 use serde_json::json;
 use futures_util::{SinkExt, StreamExt};
+use std::time::Duration;
 
-use crate::data::new_markets::get_new_markets;
-use crate::data::types::FullPayload;
-
-use crate::data::state::SharedState;
+use crate::data::pipeline::{EventSender, StateEvent};
+use crate::data::recorder::Recorder;
+use crate::data::state::{now_secs, Trade};
+use crate::config;
+use crate::data::feed::{self, FeedMessage};
+use crate::metrics::{self, MessageStatus};
 
 const WS_URL: &str = "wss://ws-live-data.polymarket.com";
+const MIN_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
 
-pub async fn run(state: SharedState) -> anyhow::Result<()> {
+/// Streams trades from the live feed, writing each raw message to `recorder` if given.
+/// Reconnects with backoff whenever the connection fails or drops.
+pub async fn run(events: EventSender, mut recorder: Option<Recorder>) -> anyhow::Result<()> {
+    let mut delay = MIN_RECONNECT_DELAY;
+    loop {
+        if stream(&events, &mut recorder).await.is_ok() {
+            // The connection came up, so the next drop starts the backoff over.
+            delay = MIN_RECONNECT_DELAY;
+        }
+        if events.is_closed() {
+            return Ok(());
+        }
+        tokio::time::sleep(delay).await;
+        delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+        metrics::get().reconnect();
+    }
+}
+
+/// Runs one connection until the server closes it.
+async fn stream(events: &EventSender, recorder: &mut Option<Recorder>) -> anyhow::Result<()> {
     let (ws_stream, _) = connect_async(WS_URL).await?;
     let (mut write, mut read) = ws_stream.split();
 
+    let subscriptions: Vec<_> = config::get()
+        .feed_subscriptions
+        .iter()
+        .filter_map(|s| {
+            let parsed = s.split_once('/');
+            if parsed.is_none() {
+                tracing::warn!("Ignoring feed subscription '{}', expected topic/type", s);
+            }
+            parsed
+        })
+        .map(|(topic, kind)| json!({ "topic": topic, "type": kind }))
+        .collect();
     let sub_req = json!({
         "action": "subscribe",
-        "subscriptions": [{
-            "topic": "activity",
-            "type": "trades"
-        }]
+        "subscriptions": subscriptions
     });
     write.send(Message::Text(sub_req.to_string().into())).await?;
-    let mut tick = 0;
     while let Some(msg) = read.next().await {
-        if let Ok(msg) = msg {
-            if let Ok(text) = msg.into_text() {
-                process_message(&state, &text).await;
+        if let Ok(msg) = msg
+            && let Ok(text) = msg.into_text()
+        {
+            if let Some(writer) = recorder.as_mut()
+                && let Err(e) = writer.record(&text)
+            {
+                tracing::error!("Recording stopped: {}", e);
+                *recorder = None;
             }
+            process_message(events, &text, false).await;
         }
-        if tick % 200 == 0 {
-            let new_markets = get_new_markets().await;
-            let mut app_state = state.lock().await;
-            app_state.set_new_markets(new_markets);
-        }
-        tick += 1;
-
     }
 
     Ok(())
 }
 
+/// Parses a raw feed message and hands what the app uses to the state actor. Replays set
+/// `restamp` so trades are dated when they are fed back, landing in the current windows.
+pub async fn process_message(events: &EventSender, msg: &str, restamp: bool) {
+    let metrics = metrics::get();
+    let message = match feed::parse(msg) {
+        Ok(message) => message,
+        Err(e) => {
+            let (topic, kind) = e.kind();
+            metrics.message(topic, kind, MessageStatus::Received);
+            metrics.message(topic, kind, MessageStatus::Failed);
+            tracing::debug!("Unparsed feed message: {}", e);
+            return;
+        }
+    };
+    let (topic, kind) = message.kind();
+    metrics.message(topic, kind, MessageStatus::Received);
+    metrics.message(topic, kind, MessageStatus::Parsed);
 
-async fn process_message(state: &SharedState, msg: &str) {
-    let mut state = state.lock().await;
-    if let Ok(full_payload) = serde_json::from_str::<FullPayload>(msg) {
-
-        let payload = full_payload.payload;
-        state.add_trade(payload.title, payload.size, payload.condition_id, payload.slug);
+    match message {
+        FeedMessage::Trade(payload) => {
+            let mut trade = Trade::from(payload);
+            if restamp {
+                trade.timestamp = now_secs();
+            }
+            let _ = events.send(StateEvent::Trade(trade)).await;
+        }
+        FeedMessage::CommentCreated(comment) => {
+            let _ = events.send(StateEvent::CommentCreated(comment)).await;
+        }
+        FeedMessage::CommentRemoved(comment) => {
+            let _ = events.send(StateEvent::CommentRemoved { id: comment.id }).await;
+        }
+        FeedMessage::ReactionCreated(reaction) => {
+            let _ = events.send(StateEvent::Reaction { comment_id: reaction.comment_id, added: true }).await;
+        }
+        FeedMessage::ReactionRemoved(reaction) => {
+            let _ = events.send(StateEvent::Reaction { comment_id: reaction.comment_id, added: false }).await;
+        }
+        FeedMessage::Subscribed { topic } => tracing::info!("Subscribed to {}", topic),
+        FeedMessage::Error(error) => tracing::warn!("Live feed error: {}", error),
+        // Counted above; nothing reads these yet.
+        FeedMessage::OrdersMatched(_) | FeedMessage::CryptoPrice(_) => {}
     }
-
 }
\ No newline at end of file
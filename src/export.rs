@@ -0,0 +1,308 @@
+use std::env;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+use dotenv::dotenv;
+use serde::Serialize;
+
+use crate::actions::orders::{open_orders, wallet_address, OpenOrder};
+use crate::data::positions::{get_positions, Position};
+use crate::data::state::{Snapshot, TimeWindow, Trade};
+
+#[derive(Clone, Copy, PartialEq, Debug, ValueEnum)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    /// Parses a format name from the config, ignoring case.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::from_str(name, true).ok()
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// The CSV header of an exported list, written even when the list is empty.
+trait Columns {
+    const COLUMNS: &'static [&'static str];
+}
+
+/// One Top Markets row. Nested statistics are flattened so the CSV has plain columns.
+#[derive(Serialize)]
+struct MarketRow<'a> {
+    rank: usize,
+    name: &'a str,
+    slug: &'a str,
+    volume: f64,
+    trades: u64,
+    outcome: Option<&'a str>,
+    last_price: Option<f64>,
+    change: Option<f64>,
+    last_trade_at: u64,
+    tags: String,
+    end_date: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct TraderRow<'a> {
+    rank: usize,
+    address: &'a str,
+    volume: f64,
+    trades: u64,
+}
+
+impl Columns for MarketRow<'_> {
+    const COLUMNS: &'static [&'static str] = &[
+        "rank", "name", "slug", "volume", "trades", "outcome", "last_price", "change", "last_trade_at", "tags", "end_date",
+    ];
+}
+
+impl Columns for TraderRow<'_> {
+    const COLUMNS: &'static [&'static str] = &["rank", "address", "volume", "trades"];
+}
+
+impl Columns for Trade {
+    const COLUMNS: &'static [&'static str] = &[
+        "timestamp", "title", "slug", "condition_id", "side", "outcome", "price", "size", "trader", "trader_name",
+    ];
+}
+
+impl Columns for Position {
+    const COLUMNS: &'static [&'static str] = &[
+        "title", "slug", "outcome", "size", "avg_price", "cur_price", "current_value", "cash_pnl", "percent_pnl",
+    ];
+}
+
+impl Columns for OpenOrder {
+    const COLUMNS: &'static [&'static str] = &[
+        "id", "market", "asset_id", "outcome", "side", "price", "original_size", "size_matched", "status", "created_at",
+    ];
+}
+
+/// Files written by one export, plus the parts that could not be fetched.
+pub struct Exported {
+    pub dir: PathBuf,
+    pub files: Vec<PathBuf>,
+    pub errors: Vec<String>,
+}
+
+impl Exported {
+    /// One line for a notice or the terminal.
+    pub fn summary(&self) -> String {
+        let mut summary = format!("Exported {} files to {}", self.files.len(), self.dir.display());
+        if !self.errors.is_empty() {
+            summary.push_str(&format!(" ({})", self.errors.join("; ")));
+        }
+        summary
+    }
+}
+
+/// Writes the Top Markets and Top Traders of `window` and the trade tape to `dir`, each
+/// to its own `pmterm-<date>-<time>-<list>` file. The wallet's positions and open orders
+/// are added when `PRIVATE_KEY` is set; failing to fetch them does not stop the export.
+pub async fn export(
+    snapshot: &Snapshot,
+    window: TimeWindow,
+    dir: &Path,
+    format: ExportFormat,
+) -> anyhow::Result<Exported> {
+    fs::create_dir_all(dir)?;
+    let prefix = chrono::Local::now().format("pmterm-%Y%m%d-%H%M%S").to_string();
+    let path = |list: &str| dir.join(format!("{}-{}.{}", prefix, list, format.extension()));
+
+    let data = snapshot.window(window);
+    let markets: Vec<MarketRow> = data
+        .top_markets
+        .iter()
+        .enumerate()
+        .map(|(i, m)| MarketRow {
+            rank: i + 1,
+            name: &m.name,
+            slug: &m.identifier,
+            volume: m.volume,
+            trades: m.trades,
+            outcome: m.price.as_ref().map(|p| p.outcome.as_str()),
+            last_price: m.price.as_ref().map(|p| p.last),
+            change: m.price.as_ref().map(|p| p.last - p.open),
+            last_trade_at: m.last_trade_at,
+            tags: m.meta.as_ref().map(|meta| meta.tags.join("|")).unwrap_or_default(),
+            end_date: m.meta.as_ref().and_then(|meta| meta.end_date),
+        })
+        .collect();
+    let traders: Vec<TraderRow> = data
+        .top_traders
+        .iter()
+        .enumerate()
+        .map(|(i, t)| TraderRow { rank: i + 1, address: &t.address, volume: t.volume, trades: t.trades })
+        .collect();
+
+    let mut exported = Exported { dir: dir.to_path_buf(), files: Vec::new(), errors: Vec::new() };
+    let markets_path = path(&format!("markets-{}", window.label()));
+    write_rows(&markets_path, format, &markets)?;
+    exported.files.push(markets_path);
+    let traders_path = path(&format!("traders-{}", window.label()));
+    write_rows(&traders_path, format, &traders)?;
+    exported.files.push(traders_path);
+    let tape_path = path("tape");
+    write_rows::<Trade>(&tape_path, format, &snapshot.tape)?;
+    exported.files.push(tape_path);
+
+    dotenv().ok();
+    let Ok(private_key) = env::var("PRIVATE_KEY") else {
+        return Ok(exported);
+    };
+
+    match wallet_address(&private_key) {
+        Ok(address) => match get_positions(&address).await {
+            Ok(positions) => {
+                let positions_path = path("positions");
+                write_rows(&positions_path, format, &positions)?;
+                exported.files.push(positions_path);
+            }
+            Err(e) => exported.errors.push(format!("positions: {}", e)),
+        },
+        Err(e) => exported.errors.push(format!("positions: {}", e)),
+    }
+    match open_orders(&private_key).await {
+        Ok(orders) => {
+            let orders_path = path("orders");
+            write_rows(&orders_path, format, &orders)?;
+            exported.files.push(orders_path);
+        }
+        Err(e) => exported.errors.push(format!("orders: {}", e)),
+    }
+
+    Ok(exported)
+}
+
+fn write_rows<T: Serialize + Columns>(path: &Path, format: ExportFormat, rows: &[T]) -> anyhow::Result<()> {
+    write_to(BufWriter::new(File::create(path)?), format, rows)
+}
+
+/// Writes `rows` as a CSV with a header line, or as a pretty-printed JSON array.
+fn write_to<T: Serialize + Columns>(out: impl Write, format: ExportFormat, rows: &[T]) -> anyhow::Result<()> {
+    match format {
+        ExportFormat::Csv => {
+            let mut writer = csv::WriterBuilder::new().has_headers(false).from_writer(out);
+            writer.write_record(T::COLUMNS)?;
+            for row in rows {
+                writer.serialize(row)?;
+            }
+            writer.flush()?;
+        }
+        ExportFormat::Json => serde_json::to_writer_pretty(out, rows)?,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_csv<T: Serialize + Columns>(rows: &[T]) -> String {
+        let mut out = Vec::new();
+        write_to(&mut out, ExportFormat::Csv, rows).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    /// The header csv would derive from a row, to keep `COLUMNS` in step with the fields.
+    fn derived_header<T: Serialize>(row: &T) -> String {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.serialize(row).unwrap();
+        let out = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+        out.lines().next().unwrap().to_string()
+    }
+
+    fn trade() -> Trade {
+        Trade {
+            timestamp: 1,
+            title: "Rain?".to_string(),
+            slug: "rain".to_string(),
+            condition_id: "0x1".to_string(),
+            side: "BUY".to_string(),
+            outcome: "Yes".to_string(),
+            price: 0.5,
+            size: 10.0,
+            trader: "0xabc".to_string(),
+            trader_name: None,
+        }
+    }
+
+    #[test]
+    fn writes_csv_with_a_header() {
+        assert_eq!(
+            to_csv(&[trade()]),
+            "timestamp,title,slug,condition_id,side,outcome,price,size,trader,trader_name\n1,Rain?,rain,0x1,BUY,Yes,0.5,10.0,0xabc,\n"
+        );
+        assert_eq!(to_csv::<Trade>(&[]), "timestamp,title,slug,condition_id,side,outcome,price,size,trader,trader_name\n");
+    }
+
+    #[test]
+    fn columns_match_the_fields() {
+        let market = MarketRow {
+            rank: 1,
+            name: "Rain?",
+            slug: "rain",
+            volume: 5.0,
+            trades: 1,
+            outcome: None,
+            last_price: None,
+            change: None,
+            last_trade_at: 1,
+            tags: String::new(),
+            end_date: None,
+        };
+        let trader = TraderRow { rank: 1, address: "0xabc", volume: 5.0, trades: 1 };
+        let position = Position {
+            title: "Rain?".to_string(),
+            slug: "rain".to_string(),
+            outcome: "Yes".to_string(),
+            size: 10.0,
+            avg_price: 0.5,
+            cur_price: 0.6,
+            current_value: 6.0,
+            cash_pnl: 1.0,
+            percent_pnl: 20.0,
+        };
+        let order = OpenOrder {
+            id: "1".to_string(),
+            market: "0x1".to_string(),
+            asset_id: "2".to_string(),
+            outcome: "Yes".to_string(),
+            side: "BUY".to_string(),
+            price: 0.5,
+            original_size: 10.0,
+            size_matched: 0.0,
+            status: "LIVE".to_string(),
+            created_at: 1,
+        };
+        assert_eq!(derived_header(&market), MarketRow::COLUMNS.join(","));
+        assert_eq!(derived_header(&trader), TraderRow::COLUMNS.join(","));
+        assert_eq!(derived_header(&trade()), Trade::COLUMNS.join(","));
+        assert_eq!(derived_header(&position), Position::COLUMNS.join(","));
+        assert_eq!(derived_header(&order), OpenOrder::COLUMNS.join(","));
+    }
+
+    #[test]
+    fn writes_json_arrays() {
+        let mut out = Vec::new();
+        write_to(&mut out, ExportFormat::Json, &[trade()]).unwrap();
+        let rows: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(rows[0]["slug"], "rain");
+        assert_eq!(rows[0]["size"], 10.0);
+        assert_eq!(rows[0]["trader_name"], serde_json::Value::Null);
+
+        let mut out = Vec::new();
+        write_to::<Trade>(&mut out, ExportFormat::Json, &[]).unwrap();
+        assert_eq!(out, b"[]");
+    }
+}
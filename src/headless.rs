@@ -0,0 +1,84 @@
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::cli::Args;
+use crate::data::closing_markets::ClosingMarket;
+use crate::data::new_markets::NewMarket;
+use crate::data::state::{now_secs, MarketData, SharedState, Snapshot, TimeWindow, TraderData};
+
+/// One line of headless output, also served by the API.
+#[derive(Serialize)]
+pub struct Report<'a> {
+    timestamp: u64,
+    running_secs: u64,
+    /// Unix seconds of the last new markets fetch, 0 before the first.
+    markets_updated: u64,
+    windows: Vec<WindowReport>,
+    new_markets: &'a [NewMarket],
+    closing_markets: &'a [ClosingMarket],
+}
+
+#[derive(Serialize)]
+struct WindowReport {
+    window: &'static str,
+    total_markets: usize,
+    total_trades: u64,
+    total_volume: f64,
+    top_markets: Vec<MarketData>,
+    top_traders: Vec<TraderData>,
+}
+
+impl<'a> Report<'a> {
+    pub fn new(snapshot: &'a Snapshot, top: usize) -> Self {
+        let windows = TimeWindow::ALL
+            .iter()
+            .map(|window| {
+                let mut data = snapshot.window(*window);
+                data.top_markets.truncate(top);
+                data.top_traders.truncate(top);
+                WindowReport {
+                    window: window.label(),
+                    total_markets: data.total_markets,
+                    total_trades: data.total_trades,
+                    total_volume: data.total_volume,
+                    top_markets: data.top_markets,
+                    top_traders: data.top_traders,
+                }
+            })
+            .collect();
+        Self {
+            timestamp: now_secs(),
+            running_secs: snapshot.time_running(),
+            markets_updated: snapshot.markets_updated,
+            windows,
+            new_markets: &snapshot.new_markets[..snapshot.new_markets.len().min(top)],
+            closing_markets: &snapshot.closing_markets[..snapshot.closing_markets.len().min(top)],
+        }
+    }
+}
+
+/// Writes a snapshot of the aggregated statistics every `args.interval` seconds
+/// until Ctrl-C.
+pub async fn run(state: SharedState, args: &Args) -> anyhow::Result<()> {
+    let mut out: Box<dyn Write> = match &args.output {
+        Some(path) => Box::new(OpenOptions::new().create(true).append(true).open(path)?),
+        None => Box::new(io::stdout()),
+    };
+
+    let mut interval = tokio::time::interval(Duration::from_secs(args.interval.max(1)));
+    // The first tick fires immediately, before any trades have arrived.
+    interval.tick().await;
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {}
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        }
+        let snapshot = state.borrow().clone();
+        serde_json::to_writer(&mut out, &Report::new(&snapshot, args.top))?;
+        writeln!(out)?;
+        out.flush()?;
+    }
+}
@@ -0,0 +1,140 @@
+use std::collections::VecDeque;
+use std::fmt::{self, Write as _};
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::sync::Mutex;
+
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt as layer_fmt, EnvFilter};
+
+use crate::config;
+use crate::data::state::now_secs;
+
+/// Warnings and errors kept for the log viewer.
+const RECENT_CAPACITY: usize = 500;
+
+static RECENT: Mutex<VecDeque<LogEntry>> = Mutex::new(VecDeque::new());
+
+/// A warning or error, as shown in the log viewer.
+#[derive(Clone)]
+pub struct LogEntry {
+    /// Unix seconds.
+    pub at: u64,
+    pub level: Level,
+    /// Module the event came from, such as `pmterm::data::ws`.
+    pub target: String,
+    /// The message followed by any fields as `name=value`.
+    pub message: String,
+}
+
+/// The most recent warnings and errors, newest first.
+pub fn recent() -> Vec<LogEntry> {
+    RECENT.lock().map(|log| log.iter().rev().cloned().collect()).unwrap_or_default()
+}
+
+/// Keeps warnings and errors in `RECENT`.
+struct RecentLayer;
+
+impl<S: Subscriber> Layer<S> for RecentLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        if *metadata.level() > Level::WARN {
+            return;
+        }
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let entry = LogEntry {
+            at: now_secs(),
+            level: *metadata.level(),
+            target: metadata.target().to_string(),
+            message: visitor.message + &visitor.fields,
+        };
+        if let Ok(mut log) = RECENT.lock() {
+            if log.len() == RECENT_CAPACITY {
+                log.pop_front();
+            }
+            log.push_back(entry);
+        }
+    }
+}
+
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+/// Starts logging to a daily rotated file in `log_dir`, filtered by `log_level`, and
+/// to the log viewer. With `console`, warnings and errors also go to stderr, for runs
+/// without the TUI. The returned guard flushes the file when dropped.
+pub fn init(console: bool) -> Option<WorkerGuard> {
+    let config = config::get();
+    let filter = EnvFilter::try_new(&config.log_level).unwrap_or_else(|e| {
+        eprintln!("Ignoring invalid log_level '{}': {}", config.log_level, e);
+        EnvFilter::new(config::DEFAULT_LOG_LEVEL)
+    });
+
+    let mut guard = None;
+    let file = if config.log_dir.is_empty() {
+        None
+    } else {
+        let appender = fs::create_dir_all(&config.log_dir).map_err(|e| e.to_string()).and_then(|_| {
+            RollingFileAppender::builder()
+                .rotation(Rotation::DAILY)
+                .filename_prefix("pmterm")
+                .filename_suffix("log")
+                .max_log_files(config.log_files.max(1))
+                .build(&config.log_dir)
+                .map_err(|e| e.to_string())
+        });
+        match appender {
+            Ok(appender) => {
+                let (writer, file_guard) = tracing_appender::non_blocking(appender);
+                guard = Some(file_guard);
+                Some(layer_fmt::layer().with_writer(writer).with_ansi(false))
+            }
+            Err(e) => {
+                eprintln!("Not logging to a file, cannot open {}: {}", config.log_dir, e);
+                None
+            }
+        }
+    };
+    let console = console.then(|| {
+        layer_fmt::layer()
+            .with_writer(io::stderr)
+            .with_ansi(io::stderr().is_terminal())
+            .with_filter(LevelFilter::WARN)
+    });
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(file)
+        .with(console)
+        .with(RecentLayer)
+        .init();
+    guard
+}
@@ -1,26 +1,89 @@
 #[allow(unused)]
 mod data;
+mod config;
 mod ui;
 mod actions;
+mod cli;
+mod headless;
+mod export;
+mod api;
+mod metrics;
+mod logging;
 
-use std::sync::Arc;
-use tokio::sync::Mutex;
-
-use crate::data::state::{AppState, SharedState};
+use clap::Parser;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    let state: SharedState = Arc::new(Mutex::new(AppState::new()));
+    let args = cli::Args::parse();
+    config::init();
+    // Kept alive until exit so the log file is flushed.
+    let _log_guard = logging::init(args.headless || args.command.is_some());
+    if let Some(cli::Command::Export { dir, format, window }) = &args.command {
+        return export_saved(dir.as_deref(), *format, *window).await;
+    }
+    // Replayed trades are restamped, so they are kept out of the saved history.
+    let db = if args.replay.is_none() { data::trade_db::TradeDb::open_configured() } else { None };
+    let (events, state) = data::pipeline::spawn(db);
+
+    let feed_events = events.clone();
+    if let Some(path) = args.replay.clone() {
+        let speed = args.speed;
+        tokio::spawn(async move {
+            if let Err(e) = data::replay::run(feed_events, &path, speed).await {
+                tracing::error!("Replay failed: {:?}", e);
+            }
+        });
+    } else {
+        let recorder = match &args.record {
+            Some(dir) => {
+                let (recorder, path) = data::recorder::Recorder::create(dir)?;
+                tracing::info!("Recording to {}", path.display());
+                Some(recorder)
+            }
+            None => None,
+        };
+        tokio::spawn(async move {
+            if let Err(e) = data::ws::run(feed_events, recorder).await {
+                tracing::error!("Live feed stopped: {:?}", e);
+            }
+        });
+    }
+
+    tokio::spawn(data::new_markets::run(events.clone()));
+    tokio::spawn(data::closing_markets::run(events.clone()));
+    tokio::spawn(data::market_meta::run(events.clone(), state.clone()));
+    api::start(state.clone(), events.clone()).await?;
+    metrics::start().await?;
 
-    let ws_state = state.clone();
-    tokio::spawn(async move {
-        if let Err(e) = data::ws::run(ws_state).await {
-            eprintln!("WebSocket error: {:?}", e);
-        }
-    });
+    if args.headless {
+        headless::run(state, &args).await?;
+    } else {
+        ui::run(state, events).await?;
+    }
+
+    Ok(())
+}
 
-    let ui_state = state.clone();
-    ui::run(ui_state).await?;
+/// Exports the statistics rebuilt from the saved trades, without connecting to the feed.
+async fn export_saved(
+    dir: Option<&std::path::Path>,
+    format: Option<export::ExportFormat>,
+    window: data::state::TimeWindow,
+) -> anyhow::Result<()> {
+    let config = config::get();
+    let format = match format {
+        Some(format) => format,
+        None => export::ExportFormat::from_name(&config.export_format)
+            .ok_or_else(|| anyhow::anyhow!("unknown export_format '{}'", config.export_format))?,
+    };
+    let dir = dir.map(|d| d.to_path_buf()).unwrap_or_else(|| config.export_dir.clone().into());
 
+    let Some(db) = data::trade_db::TradeDb::open_configured() else {
+        anyhow::bail!("No saved trades to export, trade_db is not set");
+    };
+    let (_events, state) = data::pipeline::spawn(Some(db));
+    let snapshot = state.borrow().clone();
+    let exported = export::export(&snapshot, window, &dir, format).await?;
+    println!("{}", exported.summary());
     Ok(())
-}
\ No newline at end of file
+}
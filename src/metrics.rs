@@ -0,0 +1,260 @@
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::OnceLock;
+use std::time::Instant;
+
+use axum::http::header;
+use axum::routing::get as route_get;
+use axum::Router;
+use prometheus_client::encoding::EncodeLabelSet;
+use prometheus_client::metrics::counter::Counter;
+use prometheus_client::metrics::family::Family;
+use prometheus_client::metrics::gauge::Gauge;
+use prometheus_client::metrics::histogram::{exponential_buckets, Histogram};
+use prometheus_client::registry::Registry;
+
+use crate::config;
+use crate::data::state::AppState;
+
+static METRICS: OnceLock<Metrics> = OnceLock::new();
+
+#[derive(Clone, Copy)]
+pub enum MessageStatus {
+    Received,
+    Parsed,
+    Failed,
+}
+
+impl MessageStatus {
+    fn label(self) -> &'static str {
+        match self {
+            MessageStatus::Received => "received",
+            MessageStatus::Parsed => "parsed",
+            MessageStatus::Failed => "failed",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct MessageLabels {
+    topic: String,
+    message_type: String,
+    status: &'static str,
+}
+
+/// Which remote API a request went to.
+#[derive(Clone, Copy)]
+pub enum Service {
+    Gamma,
+    Clob,
+    DataApi,
+    Rpc,
+}
+
+impl Service {
+    fn label(self) -> &'static str {
+        match self {
+            Service::Gamma => "gamma",
+            Service::Clob => "clob",
+            Service::DataApi => "data-api",
+            Service::Rpc => "rpc",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct RequestLabels {
+    service: &'static str,
+    endpoint: &'static str,
+}
+
+#[derive(Clone, Copy)]
+pub enum OrderResult {
+    Submitted,
+    Filled,
+    Rejected,
+}
+
+impl OrderResult {
+    fn label(self) -> &'static str {
+        match self {
+            OrderResult::Submitted => "submitted",
+            OrderResult::Filled => "filled",
+            OrderResult::Rejected => "rejected",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct OrderLabels {
+    result: &'static str,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct WindowLabels {
+    window: &'static str,
+}
+
+type HistogramFamily<L> = Family<L, Histogram, fn() -> Histogram>;
+
+/// Counters and gauges for the whole process, rendered in Prometheus text format.
+pub struct Metrics {
+    registry: Registry,
+    messages: Family<MessageLabels, Counter>,
+    reconnects: Counter,
+    request_duration: HistogramFamily<RequestLabels>,
+    request_errors: Family<RequestLabels, Counter>,
+    orders: Family<OrderLabels, Counter>,
+    window_markets: Family<WindowLabels, Gauge>,
+    window_traders: Family<WindowLabels, Gauge>,
+    trade_log: Gauge,
+    tape: Gauge,
+    priced_markets: Gauge,
+}
+
+fn request_histogram() -> Histogram {
+    // 10ms to about 20s.
+    Histogram::new(exponential_buckets(0.01, 2.0, 12))
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let messages = Family::<MessageLabels, Counter>::default();
+        let reconnects = Counter::default();
+        let request_duration: HistogramFamily<RequestLabels> = Family::new_with_constructor(request_histogram);
+        let request_errors = Family::<RequestLabels, Counter>::default();
+        let orders = Family::<OrderLabels, Counter>::default();
+        let window_markets = Family::<WindowLabels, Gauge>::default();
+        let window_traders = Family::<WindowLabels, Gauge>::default();
+        let trade_log = Gauge::default();
+        let tape = Gauge::default();
+        let priced_markets = Gauge::default();
+
+        let mut registry = Registry::with_prefix("pmterm");
+        registry.register("ws_messages", "Live feed messages by topic, type and parse result", messages.clone());
+        registry.register("ws_reconnects", "Times the live feed was reconnected", reconnects.clone());
+        registry.register(
+            "http_request_duration_seconds",
+            "Latency of gamma, CLOB, data API and Polygon RPC requests",
+            request_duration.clone(),
+        );
+        registry.register("http_request_errors", "Failed or non-2xx requests", request_errors.clone());
+        registry.register("orders", "Buy orders by result", orders.clone());
+        registry.register("window_markets", "Markets tracked per statistics window", window_markets.clone());
+        registry.register("window_traders", "Traders tracked per statistics window", window_traders.clone());
+        registry.register("trade_log_size", "Trades held in the rolling log", trade_log.clone());
+        registry.register("tape_size", "Trades on the tape", tape.clone());
+        registry.register("priced_markets", "Markets with live price statistics", priced_markets.clone());
+
+        Self {
+            registry,
+            messages,
+            reconnects,
+            request_duration,
+            request_errors,
+            orders,
+            window_markets,
+            window_traders,
+            trade_log,
+            tape,
+            priced_markets,
+        }
+    }
+
+    pub fn message(&self, topic: &str, message_type: &str, status: MessageStatus) {
+        let or_unknown = |label: &str| if label.is_empty() { String::from("unknown") } else { label.to_string() };
+        let labels = MessageLabels {
+            topic: or_unknown(topic),
+            message_type: or_unknown(message_type),
+            status: status.label(),
+        };
+        self.messages.get_or_create(&labels).inc();
+    }
+
+    pub fn reconnect(&self) {
+        self.reconnects.inc();
+    }
+
+    pub fn order(&self, result: OrderResult) {
+        self.orders.get_or_create(&OrderLabels { result: result.label() }).inc();
+    }
+
+    fn request(&self, service: Service, endpoint: &'static str, started: Instant, failed: bool) {
+        let labels = RequestLabels { service: service.label(), endpoint };
+        self.request_duration.get_or_create(&labels).observe(started.elapsed().as_secs_f64());
+        if failed {
+            self.request_errors.get_or_create(&labels).inc();
+        }
+    }
+
+    /// Copies the sizes of the state's collections into the gauges.
+    pub fn record_state(&self, state: &AppState) {
+        for (window, markets, traders) in state.window_sizes() {
+            let labels = WindowLabels { window: window.label() };
+            self.window_markets.get_or_create(&labels).set(markets as i64);
+            self.window_traders.get_or_create(&labels).set(traders as i64);
+        }
+        self.trade_log.set(state.trade_log.len() as i64);
+        self.tape.set(state.tape.len() as i64);
+        self.priced_markets.set(state.priced_markets() as i64);
+    }
+
+    fn render(&self) -> String {
+        let mut text = String::new();
+        // Writing to a String cannot fail.
+        let _ = prometheus_client::encoding::text::encode(&mut text, &self.registry);
+        text
+    }
+}
+
+pub fn get() -> &'static Metrics {
+    METRICS.get_or_init(Metrics::new)
+}
+
+/// Times a request and counts it as an error if it fails.
+pub async fn timed<T, E>(
+    service: Service,
+    endpoint: &'static str,
+    request: impl Future<Output = Result<T, E>>,
+) -> Result<T, E> {
+    let started = Instant::now();
+    let result = request.await;
+    get().request(service, endpoint, started, result.is_err());
+    result
+}
+
+/// `reqwest::get`, timed, with non-2xx responses also counted as errors. The response
+/// is returned as is.
+pub async fn http_get(service: Service, endpoint: &'static str, url: &str) -> reqwest::Result<reqwest::Response> {
+    let started = Instant::now();
+    let result = reqwest::get(url).await;
+    let failed = result.as_ref().map_or(true, |r| !r.status().is_success());
+    get().request(service, endpoint, started, failed);
+    result
+}
+
+/// Serves `/metrics` on `metrics_addr` in the background. Does nothing when the
+/// address is empty; fails when it is invalid or taken.
+pub async fn start() -> anyhow::Result<()> {
+    let addr = &config::get().metrics_addr;
+    if addr.is_empty() {
+        return Ok(());
+    }
+    let addr: SocketAddr = addr.parse()?;
+    let router = Router::new().route(
+        "/metrics",
+        route_get(|| async {
+            (
+                [(header::CONTENT_TYPE, "application/openmetrics-text; version=1.0.0; charset=utf-8")],
+                get().render(),
+            )
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, router).await {
+            tracing::error!("Metrics server stopped: {}", e);
+        }
+    });
+    Ok(())
+}
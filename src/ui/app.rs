@@ -1,51 +1,184 @@
-use crate::data::state::SharedState;
-use crate::ui::pages::PageType::Wallet;
-use super::pages::{Page, PageType, DashboardPage, DetailPage};
-use super::pages::WalletPage;
+use std::time::{Duration, Instant};
+
+use super::components::mouse::MouseTracker;
+use super::components::palette::CommandPalette;
+use super::pages::{Page, PageType, DashboardPage, TraderPage};
+use super::pages::{LogsPage, WalletPage, WorkspacePage};
+
+const NOTICE_DURATION: Duration = Duration::from_secs(5);
+
+/// Most pages kept behind the current one; the oldest after the dashboard are dropped.
+const MAX_HISTORY: usize = 50;
+
+/// A page instance on the navigation stack, keeping its own scroll and selection.
+pub enum OpenPage {
+    Dashboard(DashboardPage),
+    /// One market of the tabs in `App::workspace`, which every entry shares. Going back
+    /// or forward to the entry shows its market again.
+    Markets { tab: String },
+    Wallet(WalletPage),
+    Trader(TraderPage),
+    Logs(LogsPage),
+}
+
+impl OpenPage {
+    pub fn page_type(&self) -> PageType {
+        match self {
+            OpenPage::Dashboard(_) => PageType::Dashboard,
+            OpenPage::Markets { .. } => PageType::Detail,
+            OpenPage::Wallet(_) => PageType::Wallet,
+            OpenPage::Trader(_) => PageType::Trader,
+            OpenPage::Logs(_) => PageType::Logs,
+        }
+    }
+
+}
+
 pub struct App {
-    pub current_page: PageType,
-    pub dashboard: DashboardPage,
-    pub detail_page: Option<DetailPage>,
+    pub current: OpenPage,
+    pub workspace: WorkspacePage,
+    /// Pages behind the current one, oldest first. The dashboard is always at the bottom.
+    back: Vec<OpenPage>,
+    /// Pages left with Back, most recent last, until another page is opened.
+    forward: Vec<OpenPage>,
     pub should_quit: bool,
-    pub wallet_page: Option<WalletPage>,
+    pub show_help: bool,
+    /// Asking before cancelling every open order, which the palette cannot undo.
+    pub confirm_cancel_all: bool,
+    pub palette: Option<CommandPalette>,
+    pub mouse: MouseTracker,
+    notice: Option<(String, Instant)>,
 }
 
 impl App {
     pub fn new() -> Self {
         Self {
-            current_page: PageType::Dashboard,
-            dashboard: DashboardPage::new(),
-            detail_page: None,
+            current: OpenPage::Dashboard(DashboardPage::new()),
+            workspace: WorkspacePage::new(),
+            back: vec![],
+            forward: vec![],
             should_quit: false,
-            wallet_page: None
+            show_help: false,
+            confirm_cancel_all: false,
+            palette: None,
+            mouse: MouseTracker::default(),
+            notice: None,
+        }
+    }
+
+    pub fn current_page(&self) -> PageType {
+        self.current.page_type()
+    }
+
+    pub fn page_mut(&mut self) -> &mut dyn Page {
+        match &mut self.current {
+            OpenPage::Dashboard(page) => page,
+            OpenPage::Markets { .. } => &mut self.workspace,
+            OpenPage::Wallet(page) => page,
+            OpenPage::Trader(page) => page,
+            OpenPage::Logs(page) => page,
         }
     }
 
-    pub fn navigate_to(&mut self, page: PageType) {
-        self.current_page = page;
+    pub fn set_notice(&mut self, text: String) {
+        self.notice = Some((text, Instant::now()));
+    }
+
+    /// The current notice, until it has been on screen for a few seconds.
+    pub fn notice(&self) -> Option<&str> {
+        self.notice
+            .as_ref()
+            .filter(|(_, shown)| shown.elapsed() < NOTICE_DURATION)
+            .map(|(text, _)| text.as_str())
+    }
+
+    /// Opens `page` on top of the current one and forgets the forward history.
+    fn push(&mut self, page: OpenPage) {
+        self.record_tab();
+        let previous = std::mem::replace(&mut self.current, page);
+        self.back.push(previous);
+        if self.back.len() > MAX_HISTORY {
+            self.back.remove(1);
+        }
+        self.forward.clear();
     }
 
+    /// Opens the market as a tab, or switches to its tab if it is already open.
     pub fn navigate_to_detail(&mut self, title: String, content: String, identifier: String) {
-        self.detail_page = Some(DetailPage::new(title, content, identifier));
-        self.current_page = PageType::Detail;
+        self.record_tab();
+        if matches!(&self.current, OpenPage::Markets { tab } if *tab == identifier) {
+            return;
+        }
+        self.workspace.open(title, content, identifier.clone());
+        self.push(OpenPage::Markets { tab: identifier });
     }
 
     pub fn navigate_to_wallet(&mut self, title: String) {
-        self.wallet_page = Some(WalletPage::new(title));
-        self.current_page = PageType::Wallet;
+        self.push(OpenPage::Wallet(WalletPage::new(title)));
+    }
+
+    pub fn navigate_to_trader(&mut self, address: String) {
+        self.push(OpenPage::Trader(TraderPage::new(address)));
+    }
+
+    /// Opens the log viewer, or stays on it if it is already showing.
+    pub fn navigate_to_logs(&mut self) {
+        if !matches!(self.current, OpenPage::Logs(_)) {
+            self.push(OpenPage::Logs(LogsPage::new()));
+        }
     }
 
+    /// Returns to the previous page as it was left, skipping markets whose tab has been
+    /// closed. Does nothing on the dashboard.
     pub fn go_back(&mut self) {
-        match self.current_page {
-            PageType::Detail => {
-                self.current_page = PageType::Dashboard;
-                self.detail_page = None;
+        self.record_tab();
+        while let Some(previous) = self.back.pop() {
+            if !self.reopen(&previous) {
+                continue;
+            }
+            let current = std::mem::replace(&mut self.current, previous);
+            if self.is_open(&current) {
+                self.forward.push(current);
             }
-            PageType::Wallet => {
-                self.current_page = PageType::Dashboard;
-                self.wallet_page = None;
+            return;
+        }
+    }
+
+    /// Reopens the page last left with `go_back`.
+    pub fn go_forward(&mut self) {
+        self.record_tab();
+        while let Some(next) = self.forward.pop() {
+            if !self.reopen(&next) {
+                continue;
             }
-            _ => {}
+            let current = std::mem::replace(&mut self.current, next);
+            self.back.push(current);
+            return;
+        }
+    }
+
+    /// Points the current market entry at the tab on screen, which tab switches
+    /// within the workspace may have changed since it was opened.
+    fn record_tab(&mut self) {
+        if let OpenPage::Markets { tab } = &mut self.current
+            && let Some(active) = self.workspace.active_id()
+        {
+            *tab = active.to_string();
+        }
+    }
+
+    /// Shows the entry's market again. False if its tab has been closed.
+    fn reopen(&mut self, page: &OpenPage) -> bool {
+        match page {
+            OpenPage::Markets { tab } => self.workspace.show(tab),
+            _ => true,
+        }
+    }
+
+    fn is_open(&self, page: &OpenPage) -> bool {
+        match page {
+            OpenPage::Markets { tab } => self.workspace.tabs.iter().any(|t| t.id == *tab),
+            _ => true,
         }
     }
 
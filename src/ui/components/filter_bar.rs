@@ -0,0 +1,239 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::data::view::ListFilter;
+use crate::ui::theme;
+
+#[derive(Clone, Copy, PartialEq)]
+enum FilterField {
+    Text,
+    Tag,
+    MinVolume,
+    EndsWithin,
+}
+
+impl FilterField {
+    const ALL: [FilterField; 4] = [
+        FilterField::Text,
+        FilterField::Tag,
+        FilterField::MinVolume,
+        FilterField::EndsWithin,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            FilterField::Text => "Text",
+            FilterField::Tag => "Tag",
+            FilterField::MinVolume => "Min vol",
+            FilterField::EndsWithin => "Ends within",
+        }
+    }
+
+    fn next(&self) -> Self {
+        match self {
+            FilterField::Text => FilterField::Tag,
+            FilterField::Tag => FilterField::MinVolume,
+            FilterField::MinVolume => FilterField::EndsWithin,
+            FilterField::EndsWithin => FilterField::Text,
+        }
+    }
+
+    fn previous(&self) -> Self {
+        match self {
+            FilterField::Text => FilterField::EndsWithin,
+            FilterField::Tag => FilterField::Text,
+            FilterField::MinVolume => FilterField::Tag,
+            FilterField::EndsWithin => FilterField::MinVolume,
+        }
+    }
+}
+
+/// Parses "10k", "2.5m" or a plain number.
+fn parse_volume(input: &str) -> Option<f64> {
+    let input = input.trim().to_lowercase();
+    let (number, multiplier) = match input.chars().last()? {
+        'k' => (&input[..input.len() - 1], 1_000.0),
+        'm' => (&input[..input.len() - 1], 1_000_000.0),
+        'b' => (&input[..input.len() - 1], 1_000_000_000.0),
+        _ => (input.as_str(), 1.0),
+    };
+    number.parse::<f64>().ok().map(|n| n * multiplier)
+}
+
+/// Parses "30m", "6h", "2d", "1w" into seconds. A bare number is hours.
+fn parse_duration(input: &str) -> Option<u64> {
+    let input = input.trim().to_lowercase();
+    let (number, unit) = match input.chars().last()? {
+        'm' => (&input[..input.len() - 1], 60),
+        'h' => (&input[..input.len() - 1], 60 * 60),
+        'd' => (&input[..input.len() - 1], 24 * 60 * 60),
+        'w' => (&input[..input.len() - 1], 7 * 24 * 60 * 60),
+        _ => (input.as_str(), 60 * 60),
+    };
+    // Out-of-range input is rejected rather than wrapped or panicking.
+    number.parse::<u64>().ok()?.checked_mul(unit)
+}
+
+/// Editable filter inputs shown above the dashboard panels.
+#[derive(Default)]
+pub struct FilterBar {
+    pub editing: bool,
+    field: Option<FilterField>,
+    text: String,
+    tag: String,
+    min_volume: String,
+    ends_within: String,
+}
+
+impl FilterBar {
+    pub fn open(&mut self) {
+        self.editing = true;
+        self.field.get_or_insert(FilterField::Text);
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Whether the bar needs to be on screen.
+    pub fn is_visible(&self) -> bool {
+        self.editing || FilterField::ALL.iter().any(|f| !self.value(*f).is_empty())
+    }
+
+    fn value(&self, field: FilterField) -> &str {
+        match field {
+            FilterField::Text => &self.text,
+            FilterField::Tag => &self.tag,
+            FilterField::MinVolume => &self.min_volume,
+            FilterField::EndsWithin => &self.ends_within,
+        }
+    }
+
+    fn value_mut(&mut self, field: FilterField) -> &mut String {
+        match field {
+            FilterField::Text => &mut self.text,
+            FilterField::Tag => &mut self.tag,
+            FilterField::MinVolume => &mut self.min_volume,
+            FilterField::EndsWithin => &mut self.ends_within,
+        }
+    }
+
+    fn is_valid(&self, field: FilterField) -> bool {
+        let value = self.value(field);
+        value.is_empty()
+            || match field {
+                FilterField::MinVolume => parse_volume(value).is_some(),
+                FilterField::EndsWithin => parse_duration(value).is_some(),
+                FilterField::Text | FilterField::Tag => true,
+            }
+    }
+
+    /// Handles a key while editing. Returns whether the filter values changed.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let field = *self.field.get_or_insert(FilterField::Text);
+        match key.code {
+            KeyCode::Enter | KeyCode::Esc => {
+                self.editing = false;
+                false
+            }
+            KeyCode::Tab | KeyCode::Right => {
+                self.field = Some(field.next());
+                false
+            }
+            KeyCode::BackTab | KeyCode::Left => {
+                self.field = Some(field.previous());
+                false
+            }
+            KeyCode::Backspace => self.value_mut(field).pop().is_some(),
+            KeyCode::Char(c) => {
+                self.value_mut(field).push(c);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// The criteria to apply. Invalid numeric inputs are ignored until fixed.
+    pub fn filter(&self) -> ListFilter {
+        let non_empty = |s: &str| {
+            let s = s.trim().to_lowercase();
+            (!s.is_empty()).then_some(s)
+        };
+        ListFilter {
+            text: non_empty(&self.text),
+            tag: non_empty(&self.tag),
+            min_volume: parse_volume(&self.min_volume).unwrap_or(0.0),
+            ends_within: parse_duration(&self.ends_within),
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let theme = theme::current();
+        let mut spans = Vec::new();
+        for field in FilterField::ALL {
+            let focused = self.editing && self.field == Some(field);
+            let label_style = if focused { theme.key() } else { theme.muted() };
+            let value_style = if self.is_valid(field) {
+                Style::default()
+            } else {
+                Style::default().fg(theme.error)
+            };
+            let cursor = if focused { "_" } else { "" };
+            spans.push(Span::styled(format!("{}: ", field.label()), label_style));
+            spans.push(Span::styled(format!("{}{}", self.value(field), cursor), value_style));
+            spans.push(Span::raw("   "));
+        }
+
+        let title = if self.editing {
+            "Filter [Tab next field, Enter/Esc done]"
+        } else {
+            "Filter"
+        };
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(theme.border(self.editing));
+
+        frame.render_widget(Paragraph::new(Line::from(spans)).block(block), area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_volumes_with_suffixes() {
+        assert_eq!(parse_volume("250"), Some(250.0));
+        assert_eq!(parse_volume(" 10k "), Some(10_000.0));
+        assert_eq!(parse_volume("1.5M"), Some(1_500_000.0));
+        assert_eq!(parse_volume("2b"), Some(2_000_000_000.0));
+        assert_eq!(parse_volume(""), None);
+        assert_eq!(parse_volume("k"), None);
+        assert_eq!(parse_volume("lots"), None);
+    }
+
+    #[test]
+    fn parses_durations_into_seconds() {
+        assert_eq!(parse_duration("30m"), Some(30 * 60));
+        assert_eq!(parse_duration("6H"), Some(6 * 60 * 60));
+        assert_eq!(parse_duration("2d"), Some(2 * 24 * 60 * 60));
+        assert_eq!(parse_duration("1w"), Some(7 * 24 * 60 * 60));
+        assert_eq!(parse_duration("3"), Some(3 * 60 * 60));
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("1.5h"), None);
+        assert_eq!(parse_duration("-1d"), None);
+    }
+
+    #[test]
+    fn rejects_durations_that_overflow() {
+        assert_eq!(parse_duration(&format!("{}w", u64::MAX / 60)), None);
+        assert_eq!(parse_duration(&u64::MAX.to_string()), None);
+    }
+}
@@ -0,0 +1,77 @@
+use chrono::{DateTime, Local};
+
+use crate::data::prices::OutcomeStats;
+
+pub fn format_volume(volume: f64) -> String {
+    if volume >= 1_000_000_000.0 {
+        format!("{:.2}B", volume / 1_000_000_000.0)
+    } else if volume >= 1_000_000.0 {
+        format!("{:.2}M", volume / 1_000_000.0)
+    } else if volume >= 1_000.0 {
+        format!("{:.2}K", volume / 1_000.0)
+    } else {
+        format!("{:.2}", volume)
+    }
+}
+
+pub fn format_address(address: &str) -> String {
+    if address.len() <= 10 {
+        address.to_string()
+    } else {
+        format!("{}...{}", &address[..6], &address[address.len() - 4..])
+    }
+}
+
+/// Formats a unix timestamp (seconds) as local wall-clock time.
+pub fn format_time(timestamp: u64) -> String {
+    DateTime::from_timestamp(timestamp as i64, 0)
+        .map(|t| t.with_timezone(&Local).format("%H:%M:%S").to_string())
+        .unwrap_or_else(|| "--:--:--".to_string())
+}
+
+/// Formats a unix timestamp (seconds) as a local date and time, for spans longer than a day.
+pub fn format_date_time(timestamp: u64) -> String {
+    DateTime::from_timestamp(timestamp as i64, 0)
+        .map(|t| t.with_timezone(&Local).format("%m-%d %H:%M").to_string())
+        .unwrap_or_else(|| "--".to_string())
+}
+
+/// Time left as its two largest units, e.g. `3d 4h`, `5h 12m` or `45s`.
+pub fn format_countdown(secs: u64) -> String {
+    let (days, hours, minutes) = (secs / 86_400, secs / 3_600 % 24, secs / 60 % 60);
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m {}s", minutes, secs % 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+/// Signed price change, e.g. `+0.04`.
+pub fn format_change(change: f64) -> String {
+    format!("{:+.2}", change)
+}
+
+/// One-line summary of an outcome's live price, e.g. `Yes 0.53 (+0.04)`.
+pub fn format_price(stats: &OutcomeStats) -> String {
+    format!("{} {:.2} ({})", stats.outcome, stats.last, format_change(stats.change()))
+}
+
+/// Renders values as a row of block characters scaled to the largest value.
+pub fn sparkline(values: &[f64]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let max = values.iter().cloned().fold(0.0, f64::max);
+    values
+        .iter()
+        .map(|v| {
+            if max <= 0.0 || *v <= 0.0 {
+                ' '
+            } else {
+                BARS[((v / max) * (BARS.len() - 1) as f64).round() as usize]
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,6 @@
+pub mod format;
+pub mod scroll;
+pub mod filter_bar;
+pub mod palette;
+pub mod popup;
+pub mod mouse;
@@ -0,0 +1,69 @@
+use std::time::{Duration, Instant};
+
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Position, Rect};
+
+/// Two left clicks on the same cell within this long count as a double click.
+const DOUBLE_CLICK: Duration = Duration::from_millis(400);
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum MouseKind {
+    Click,
+    DoubleClick,
+    ScrollUp,
+    ScrollDown,
+}
+
+/// A mouse event reduced to what pages react to.
+#[derive(Clone, Copy)]
+pub struct Mouse {
+    pub kind: MouseKind,
+    pub position: Position,
+}
+
+impl Mouse {
+    pub fn is_click(&self) -> bool {
+        matches!(self.kind, MouseKind::Click | MouseKind::DoubleClick)
+    }
+
+    pub fn is_in(&self, area: Rect) -> bool {
+        area.contains(self.position)
+    }
+
+    /// Index of the row under the pointer among the rows of a bordered panel,
+    /// skipping `header` lines below the top border.
+    pub fn row_in(&self, area: Rect, header: u16) -> Option<usize> {
+        let first = area.y + 1 + header;
+        let inside = self.position.x > area.x
+            && self.position.x < area.right().saturating_sub(1)
+            && self.position.y >= first
+            && self.position.y < area.bottom().saturating_sub(1);
+        inside.then(|| (self.position.y - first) as usize)
+    }
+}
+
+/// Turns raw crossterm events into `Mouse` events, recognising double clicks.
+#[derive(Default)]
+pub struct MouseTracker {
+    last_click: Option<(Instant, Position)>,
+}
+
+impl MouseTracker {
+    pub fn translate(&mut self, event: MouseEvent) -> Option<Mouse> {
+        let position = Position::new(event.column, event.row);
+        let kind = match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let double = self
+                    .last_click
+                    .is_some_and(|(at, last)| last == position && at.elapsed() < DOUBLE_CLICK);
+                // A third click starts a new pair rather than counting as another double.
+                self.last_click = (!double).then(|| (Instant::now(), position));
+                if double { MouseKind::DoubleClick } else { MouseKind::Click }
+            }
+            MouseEventKind::ScrollUp => MouseKind::ScrollUp,
+            MouseEventKind::ScrollDown => MouseKind::ScrollDown,
+            _ => return None,
+        };
+        Some(Mouse { kind, position })
+    }
+}
@@ -0,0 +1,288 @@
+use std::cmp::Reverse;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState},
+};
+
+use crate::ui::components::mouse::{Mouse, MouseKind};
+use crate::ui::components::popup::centered;
+use crate::ui::keymap::{Action, KeyMap};
+use crate::ui::theme;
+
+/// Something the palette can run.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Command {
+    /// A keymap action handled by the current page.
+    Page(Action),
+    /// A keymap action handled by the runner on every page.
+    Global(Action),
+    OpenMarket,
+    Wallet,
+    CancelAll,
+    Quit,
+}
+
+impl Command {
+    fn label(&self) -> &'static str {
+        match self {
+            Command::Page(action) | Command::Global(action) => action.description(),
+            Command::OpenMarket => "Open market by slug",
+            Command::Wallet => "Go to wallet",
+            Command::CancelAll => "Cancel all open orders",
+            Command::Quit => "Quit",
+        }
+    }
+
+    /// Prompt for the argument the command needs, if it takes one.
+    fn prompt(&self) -> Option<&'static str> {
+        match self {
+            Command::OpenMarket => Some("Market slug"),
+            _ => None,
+        }
+    }
+}
+
+pub enum PaletteEvent {
+    None,
+    Close,
+    /// Run the command with the typed argument (empty when it takes none).
+    Run(Command, String),
+}
+
+struct Entry {
+    command: Command,
+    keys: String,
+}
+
+const MAX_VISIBLE: usize = 10;
+
+/// `:` popup that fuzzy matches over the commands available on the current page.
+pub struct CommandPalette {
+    input: String,
+    entries: Vec<Entry>,
+    /// Indices into `entries`, best match first.
+    matches: Vec<usize>,
+    selected: usize,
+    /// Command waiting for its argument to be typed.
+    pending: Option<Command>,
+    matcher: SkimMatcherV2,
+    /// Popup and list areas as drawn last frame, for mouse hit-testing.
+    popup_area: Rect,
+    list_area: Rect,
+}
+
+impl CommandPalette {
+    pub fn new(global: &KeyMap, keymap: &KeyMap) -> Self {
+        let mut entries: Vec<Entry> = keymap
+            .actions()
+            .filter(|(action, _)| action.is_command() && !matches!(action, Action::Quit | Action::OpenWallet))
+            .map(|(action, keys)| Entry { command: Command::Page(action), keys })
+            .collect();
+        entries.extend(
+            global
+                .actions()
+                .filter(|(action, _)| action.is_command())
+                .map(|(action, keys)| Entry { command: Command::Global(action), keys }),
+        );
+        entries.push(Entry { command: Command::OpenMarket, keys: String::new() });
+        entries.push(Entry { command: Command::Wallet, keys: keymap.keys_label(Action::OpenWallet) });
+        entries.push(Entry { command: Command::CancelAll, keys: String::new() });
+        entries.push(Entry { command: Command::Quit, keys: keymap.keys_label(Action::Quit) });
+
+        let mut palette = Self {
+            input: String::new(),
+            entries,
+            matches: vec![],
+            selected: 0,
+            pending: None,
+            matcher: SkimMatcherV2::default(),
+            popup_area: Rect::default(),
+            list_area: Rect::default(),
+        };
+        palette.refresh();
+        palette
+    }
+
+    fn refresh(&mut self) {
+        let mut scored: Vec<(i64, usize)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, entry)| {
+                if self.input.is_empty() {
+                    return Some((0, idx));
+                }
+                self.matcher
+                    .fuzzy_match(entry.command.label(), &self.input)
+                    .map(|score| (score, idx))
+            })
+            .collect();
+        // Stable sort keeps the listed order among equal scores.
+        scored.sort_by_key(|(score, _)| Reverse(*score));
+        self.matches = scored.into_iter().map(|(_, idx)| idx).collect();
+        self.selected = 0;
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> PaletteEvent {
+        if let Some(command) = self.pending {
+            return match key.code {
+                KeyCode::Esc => PaletteEvent::Close,
+                KeyCode::Enter if !self.input.trim().is_empty() => {
+                    PaletteEvent::Run(command, self.input.trim().to_string())
+                }
+                KeyCode::Backspace => {
+                    self.input.pop();
+                    PaletteEvent::None
+                }
+                KeyCode::Char(c) => {
+                    self.input.push(c);
+                    PaletteEvent::None
+                }
+                _ => PaletteEvent::None,
+            };
+        }
+
+        match key.code {
+            KeyCode::Esc => PaletteEvent::Close,
+            KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+                PaletteEvent::None
+            }
+            KeyCode::Down | KeyCode::Tab => {
+                if self.selected + 1 < self.matches.len() {
+                    self.selected += 1;
+                }
+                PaletteEvent::None
+            }
+            KeyCode::Enter => {
+                let Some(command) = self.matches.get(self.selected).map(|idx| self.entries[*idx].command) else {
+                    return PaletteEvent::None;
+                };
+                if command.prompt().is_some() {
+                    self.pending = Some(command);
+                    self.input.clear();
+                    PaletteEvent::None
+                } else {
+                    PaletteEvent::Run(command, String::new())
+                }
+            }
+            KeyCode::Backspace => {
+                self.input.pop();
+                self.refresh();
+                PaletteEvent::None
+            }
+            KeyCode::Char(c) => {
+                self.input.push(c);
+                self.refresh();
+                PaletteEvent::None
+            }
+            _ => PaletteEvent::None,
+        }
+    }
+
+    /// Wheel moves the selection, a click selects a row and a double click runs it.
+    /// Clicking outside the popup closes it.
+    pub fn handle_mouse(&mut self, mouse: Mouse) -> PaletteEvent {
+        if mouse.is_click() && !mouse.is_in(self.popup_area) {
+            return PaletteEvent::Close;
+        }
+        if self.pending.is_some() {
+            return PaletteEvent::None;
+        }
+        match mouse.kind {
+            MouseKind::ScrollUp => self.handle_key(KeyEvent::from(KeyCode::Up)),
+            MouseKind::ScrollDown => self.handle_key(KeyEvent::from(KeyCode::Down)),
+            MouseKind::Click | MouseKind::DoubleClick => {
+                if !mouse.is_in(self.list_area) {
+                    return PaletteEvent::None;
+                }
+                let index = self.offset() + (mouse.position.y - self.list_area.y) as usize;
+                if index >= self.matches.len() {
+                    return PaletteEvent::None;
+                }
+                self.selected = index;
+                if mouse.kind == MouseKind::DoubleClick {
+                    self.handle_key(KeyEvent::from(KeyCode::Enter))
+                } else {
+                    PaletteEvent::None
+                }
+            }
+        }
+    }
+
+    /// Index of the first match shown in the list.
+    fn offset(&self) -> usize {
+        self.selected.saturating_sub(MAX_VISIBLE - 1)
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let height = if self.pending.is_some() { 3 } else { MAX_VISIBLE as u16 + 4 };
+        let popup = centered(area, 64, height);
+        self.popup_area = popup;
+        frame.render_widget(Clear, popup);
+
+        let prompt = self.pending.and_then(|c| c.prompt()).unwrap_or(":");
+        let title = match self.pending {
+            Some(command) => format!(" {} ", command.label()),
+            None => " Command Palette ".to_string(),
+        };
+        let theme = theme::current();
+        frame.render_widget(Block::default().style(Style::default().bg(theme.popup_bg)), popup);
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(theme.border(true));
+        let inner = block.inner(popup);
+        frame.render_widget(block, popup);
+
+        let input = Line::from(vec![
+            Span::styled(format!("{} ", prompt), theme.key()),
+            Span::raw(self.input.clone()),
+            Span::styled("│", theme.muted()),
+        ]);
+        frame.render_widget(Paragraph::new(input), Rect { height: 1, ..inner });
+
+        if self.pending.is_some() {
+            return;
+        }
+
+        let list_area = Rect {
+            y: inner.y + 2,
+            height: inner.height.saturating_sub(2),
+            ..inner
+        };
+        self.list_area = list_area;
+        let offset = self.offset();
+        let rows: Vec<Row> = self
+            .matches
+            .iter()
+            .skip(offset)
+            .take(MAX_VISIBLE)
+            .map(|idx| {
+                let entry = &self.entries[*idx];
+                Row::new(vec![
+                    Cell::from(entry.command.label()),
+                    Cell::from(Span::styled(entry.keys.clone(), theme.muted())),
+                ])
+            })
+            .collect();
+
+        let mut state = TableState::default();
+        if !self.matches.is_empty() {
+            state.select(Some(self.selected - offset));
+        }
+        frame.render_stateful_widget(
+            Table::new(rows, [Constraint::Fill(1), Constraint::Length(14)])
+                .row_highlight_style(theme.highlight()),
+            list_area,
+            &mut state,
+        );
+    }
+}
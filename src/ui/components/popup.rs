@@ -0,0 +1,101 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Rect},
+    style::Style,
+    text::Span,
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, Wrap},
+};
+
+use crate::ui::keymap::KeyMap;
+use crate::ui::theme;
+
+/// A `width` x `height` rect centred in `area`, shrunk to fit.
+pub fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
+
+/// `?` overlay listing every binding of the current page plus the global ones.
+pub fn render_help(
+    frame: &mut Frame,
+    area: Rect,
+    global: &KeyMap,
+    page: &KeyMap,
+    extras: &[(&'static str, &'static str)],
+) {
+    let theme = theme::current();
+    let key_style = theme.key();
+    let mut rows: Vec<Row> = page
+        .actions()
+        .chain(global.actions())
+        .filter(|(_, keys)| !keys.is_empty())
+        .map(|(action, keys)| {
+            Row::new(vec![
+                Cell::from(Span::styled(keys, key_style)),
+                Cell::from(action.description()),
+            ])
+        })
+        .collect();
+    rows.extend(extras.iter().map(|(keys, description)| {
+        Row::new(vec![
+            Cell::from(Span::styled(*keys, key_style)),
+            Cell::from(*description),
+        ])
+    }));
+
+    let popup = centered(area, 56, rows.len() as u16 + 2);
+    frame.render_widget(Clear, popup);
+    let block = Block::default()
+        .title(" Help ")
+        .title_bottom(" any key to close ")
+        .borders(Borders::ALL)
+        .border_style(theme.border(true));
+    frame.render_widget(
+        Table::new(rows, [Constraint::Length(16), Constraint::Fill(1)])
+            .block(block)
+            .style(Style::default().bg(theme.popup_bg)),
+        popup,
+    );
+}
+
+/// Short status message, such as the result of a palette command.
+pub fn render_notice(frame: &mut Frame, area: Rect, text: &str) {
+    let width = (text.len() as u16 + 4).clamp(20, 80);
+    let popup = Rect {
+        y: area.bottom().saturating_sub(5),
+        ..centered(area, width, 3)
+    };
+    frame.render_widget(Clear, popup);
+    let theme = theme::current();
+    frame.render_widget(
+        Paragraph::new(text.to_string())
+            .wrap(Wrap { trim: true })
+            .style(Style::default().bg(theme.popup_bg))
+            .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(theme.accent))),
+        popup,
+    );
+}
+
+/// Yes/no prompt before an action that cannot be undone.
+pub fn render_confirm(frame: &mut Frame, area: Rect, question: &str) {
+    let theme = theme::current();
+    let popup = centered(area, (question.len() as u16 + 4).max(44), 3);
+    frame.render_widget(Clear, popup);
+    let block = Block::default()
+        .title(" Confirm ")
+        .title_bottom(" y to confirm, any other key to cancel ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.error));
+    frame.render_widget(
+        Paragraph::new(question.to_string())
+            .style(Style::default().bg(theme.popup_bg))
+            .block(block),
+        popup,
+    );
+}
@@ -0,0 +1,81 @@
+use std::ops::Range;
+
+use ratatui::text::Line;
+
+/// Selection and scroll offset for a list that only builds the rows it can show.
+#[derive(Clone, Copy, Default)]
+pub struct ScrollState {
+    pub selected: usize,
+    offset: usize,
+    /// Rows visible at the last render, used as the PageUp/PageDown step.
+    page: usize,
+}
+
+impl ScrollState {
+    pub fn up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn down(&mut self, len: usize) {
+        if self.selected + 1 < len {
+            self.selected += 1;
+        }
+    }
+
+    pub fn page_up(&mut self) {
+        self.selected = self.selected.saturating_sub(self.page.max(1));
+    }
+
+    pub fn page_down(&mut self, len: usize) {
+        self.selected = (self.selected + self.page.max(1)).min(len.saturating_sub(1));
+    }
+
+    pub fn home(&mut self) {
+        self.selected = 0;
+    }
+
+    pub fn end(&mut self, len: usize) {
+        self.selected = len.saturating_sub(1);
+    }
+
+    /// Clamps the selection to `len` items, scrolls just far enough to keep it inside
+    /// a viewport of `height` rows and returns the item range to draw.
+    pub fn visible(&mut self, len: usize, height: usize) -> Range<usize> {
+        self.page = height;
+        self.selected = self.selected.min(len.saturating_sub(1));
+        if height == 0 || len == 0 {
+            self.offset = 0;
+            return 0..0;
+        }
+        if self.selected < self.offset {
+            self.offset = self.selected;
+        } else if self.selected >= self.offset + height {
+            self.offset = self.selected + 1 - height;
+        }
+        self.offset = self.offset.min(len.saturating_sub(height));
+        self.offset..(self.offset + height).min(len)
+    }
+
+    /// Selects the item shown on `row` of the last rendered range. Returns false when
+    /// the row is past the end of the list.
+    pub fn select_visible(&mut self, row: usize, len: usize) -> bool {
+        let index = self.offset + row;
+        if index < len && row < self.page {
+            self.selected = index;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Selection relative to the range returned by `visible`.
+    pub fn relative(&self) -> usize {
+        self.selected - self.offset
+    }
+
+    /// "n of m" indicator for the panel border.
+    pub fn position(&self, len: usize) -> Line<'static> {
+        let current = if len == 0 { 0 } else { self.selected + 1 };
+        Line::from(format!(" {} of {} ", current, len)).right_aligned()
+    }
+}
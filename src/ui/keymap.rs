@@ -0,0 +1,479 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::text::{Line, Span};
+
+use crate::config::{self, KeysConfig};
+use super::pages::PageType;
+use super::theme;
+
+static KEYMAPS: OnceLock<KeyMaps> = OnceLock::new();
+
+/// Something a key can be bound to. Pages decide what each action means for them.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Action {
+    Quit,
+    Back,
+    Up,
+    Down,
+    Left,
+    Right,
+    NextPanel,
+    PageUp,
+    PageDown,
+    Home,
+    End,
+    Select,
+    OpenTrader,
+    OpenWallet,
+    CycleWindow,
+    CycleSort,
+    ReverseSort,
+    EditFilter,
+    ClearFilter,
+    BuyYes,
+    BuyNo,
+    CycleRange,
+    Approve,
+    Help,
+    CommandPalette,
+    CycleTheme,
+    Forward,
+    NextTab,
+    PrevTab,
+    CloseTab,
+    ToggleSplit,
+    Export,
+    OpenLogs,
+    ToggleClosing,
+}
+
+impl Action {
+    const NAMES: [(Action, &'static str); 34] = [
+        (Action::Quit, "quit"),
+        (Action::Back, "back"),
+        (Action::Up, "up"),
+        (Action::Down, "down"),
+        (Action::Left, "left"),
+        (Action::Right, "right"),
+        (Action::NextPanel, "next_panel"),
+        (Action::PageUp, "page_up"),
+        (Action::PageDown, "page_down"),
+        (Action::Home, "home"),
+        (Action::End, "end"),
+        (Action::Select, "select"),
+        (Action::OpenTrader, "open_trader"),
+        (Action::OpenWallet, "open_wallet"),
+        (Action::CycleWindow, "cycle_window"),
+        (Action::CycleSort, "cycle_sort"),
+        (Action::ReverseSort, "reverse_sort"),
+        (Action::EditFilter, "edit_filter"),
+        (Action::ClearFilter, "clear_filter"),
+        (Action::BuyYes, "buy_yes"),
+        (Action::BuyNo, "buy_no"),
+        (Action::CycleRange, "cycle_range"),
+        (Action::Approve, "approve"),
+        (Action::Help, "help"),
+        (Action::CommandPalette, "command_palette"),
+        (Action::CycleTheme, "cycle_theme"),
+        (Action::Forward, "forward"),
+        (Action::NextTab, "next_tab"),
+        (Action::PrevTab, "prev_tab"),
+        (Action::CloseTab, "close_tab"),
+        (Action::ToggleSplit, "toggle_split"),
+        (Action::Export, "export"),
+        (Action::OpenLogs, "open_logs"),
+        (Action::ToggleClosing, "toggle_closing"),
+    ];
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::NAMES.iter().find(|(_, n)| *n == name).map(|(a, _)| *a)
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Action::Quit => "Quit",
+            Action::Back => "Go back",
+            Action::Up => "Move up",
+            Action::Down => "Move down",
+            Action::Left => "Previous panel",
+            Action::Right => "Next panel",
+            Action::NextPanel => "Switch panel",
+            Action::PageUp => "Page up",
+            Action::PageDown => "Page down",
+            Action::Home => "Jump to first",
+            Action::End => "Jump to last",
+            Action::Select => "Open selection",
+            Action::OpenTrader => "Open trader",
+            Action::OpenWallet => "Go to wallet",
+            Action::CycleWindow => "Toggle statistics window",
+            Action::CycleSort => "Cycle sort key",
+            Action::ReverseSort => "Reverse sort order",
+            Action::EditFilter => "Edit filter",
+            Action::ClearFilter => "Clear filter",
+            Action::BuyYes => "Buy Yes",
+            Action::BuyNo => "Buy No",
+            Action::CycleRange => "Cycle chart range",
+            Action::Approve => "Run approvals",
+            Action::Help => "Show help",
+            Action::CommandPalette => "Open command palette",
+            Action::CycleTheme => "Switch colour theme",
+            Action::Forward => "Go forward",
+            Action::NextTab => "Next market tab",
+            Action::PrevTab => "Previous market tab",
+            Action::CloseTab => "Close market tab",
+            Action::ToggleSplit => "Toggle split view",
+            Action::Export => "Export lists to files",
+            Action::OpenLogs => "Show warnings and errors",
+            Action::ToggleClosing => "Toggle new and closing soon markets",
+        }
+    }
+
+    /// Whether the action makes sense to run from the command palette, as opposed to
+    /// moving a selection around.
+    pub fn is_command(&self) -> bool {
+        !matches!(
+            self,
+            Action::Up
+                | Action::Down
+                | Action::Left
+                | Action::Right
+                | Action::NextPanel
+                | Action::PageUp
+                | Action::PageDown
+                | Action::Home
+                | Action::End
+                | Action::Select
+                | Action::Help
+                | Action::CommandPalette
+        )
+    }
+}
+
+/// A key plus the Ctrl/Alt modifiers it must be pressed with.
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct KeyBinding {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    /// Parses names like "q", "S", "ctrl+c", "pageup" or "f1".
+    fn parse(input: &str) -> Option<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = input;
+        loop {
+            let lower = rest.to_lowercase();
+            if lower.starts_with("ctrl+") && rest.len() > 5 {
+                modifiers |= KeyModifiers::CONTROL;
+                rest = &rest[5..];
+            } else if lower.starts_with("alt+") && rest.len() > 4 {
+                modifiers |= KeyModifiers::ALT;
+                rest = &rest[4..];
+            } else {
+                break;
+            }
+        }
+
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match rest.to_lowercase().as_str() {
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "enter" => KeyCode::Enter,
+                "esc" => KeyCode::Esc,
+                "backspace" => KeyCode::Backspace,
+                "delete" => KeyCode::Delete,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "space" => KeyCode::Char(' '),
+                other => KeyCode::F(other.strip_prefix('f')?.parse().ok()?),
+            },
+        };
+        Some(Self { code, modifiers })
+    }
+
+    fn matches(&self, key: &KeyEvent) -> bool {
+        // Shift is already reflected in the character, so only Ctrl and Alt are compared.
+        let relevant = KeyModifiers::CONTROL | KeyModifiers::ALT;
+        self.code == key.code && self.modifiers == key.modifiers & relevant
+    }
+
+    fn label(&self) -> String {
+        let key = match self.code {
+            KeyCode::Up => "↑".to_string(),
+            KeyCode::Down => "↓".to_string(),
+            KeyCode::Left => "←".to_string(),
+            KeyCode::Right => "→".to_string(),
+            KeyCode::PageUp => "PgUp".to_string(),
+            KeyCode::PageDown => "PgDn".to_string(),
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::F(n) => format!("F{}", n),
+            other => format!("{:?}", other),
+        };
+        let mut label = String::new();
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            label.push_str("Ctrl+");
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            label.push_str("Alt+");
+        }
+        label + &key
+    }
+}
+
+/// Key bindings for one page.
+pub struct KeyMap {
+    bindings: Vec<(Action, Vec<KeyBinding>)>,
+}
+
+impl KeyMap {
+    fn new(defaults: &[(Action, &[&str])]) -> Self {
+        let bindings = defaults
+            .iter()
+            .map(|(action, keys)| (*action, keys.iter().filter_map(|k| KeyBinding::parse(k)).collect()))
+            .collect();
+        Self { bindings }
+    }
+
+    /// Replaces the keys of any action named in `overrides`. Only actions the page
+    /// already has can be rebound; unknown names and keys are skipped.
+    fn apply(&mut self, overrides: &HashMap<String, Vec<String>>) {
+        for (name, keys) in overrides {
+            let Some(action) = Action::from_name(name) else {
+                continue;
+            };
+            let Some((_, bound)) = self.bindings.iter_mut().find(|(a, _)| *a == action) else {
+                continue;
+            };
+            *bound = keys.iter().filter_map(|k| KeyBinding::parse(k)).collect();
+        }
+    }
+
+    pub fn action(&self, key: &KeyEvent) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|(_, keys)| keys.iter().any(|k| k.matches(key)))
+            .map(|(action, _)| *action)
+    }
+
+    /// Every action on the page with a display string of its keys, in binding order.
+    pub fn actions(&self) -> impl Iterator<Item = (Action, String)> + '_ {
+        self.bindings.iter().map(|(action, _)| (*action, self.keys_label(*action)))
+    }
+
+    /// Display string for the keys bound to `action`, such as "Esc/Backspace".
+    pub fn keys_label(&self, action: Action) -> String {
+        self.bindings
+            .iter()
+            .find(|(a, _)| *a == action)
+            .map(|(_, keys)| keys.iter().map(|k| k.label()).collect::<Vec<_>>().join("/"))
+            .unwrap_or_default()
+    }
+
+    /// Help bar listing `entries` with whatever keys they are currently bound to.
+    /// Actions left without any key are skipped.
+    pub fn help_line(&self, entries: &[(Action, &str)]) -> Line<'static> {
+        let key_style = theme::current().key();
+        let mut spans = Vec::new();
+        for (action, label) in entries {
+            let keys = self.keys_label(*action);
+            if keys.is_empty() {
+                continue;
+            }
+            spans.push(Span::styled(keys, key_style));
+            spans.push(Span::raw(format!(" {}  ", label)));
+        }
+        Line::from(spans)
+    }
+}
+
+/// Key maps for every page, built from the defaults and the `[keys]` config section.
+pub struct KeyMaps {
+    /// Bindings handled by the runner on every page.
+    pub global: KeyMap,
+    pub dashboard: KeyMap,
+    pub detail: KeyMap,
+    pub wallet: KeyMap,
+    pub trader: KeyMap,
+    pub logs: KeyMap,
+}
+
+impl KeyMaps {
+    fn load(config: &KeysConfig) -> Self {
+        for (section, overrides) in [
+            ("global", &config.global),
+            ("dashboard", &config.dashboard),
+            ("detail", &config.detail),
+            ("wallet", &config.wallet),
+            ("trader", &config.trader),
+            ("logs", &config.logs),
+        ] {
+            for (name, keys) in overrides {
+                if Action::from_name(name).is_none() {
+                    tracing::warn!("Ignoring unknown action '{}' in [keys.{}]", name, section);
+                }
+                for key in keys.iter().filter(|k| KeyBinding::parse(k).is_none()) {
+                    tracing::warn!("Ignoring unknown key '{}' for {} in [keys.{}]", key, name, section);
+                }
+            }
+        }
+
+        let mut maps = Self {
+            global: KeyMap::new(&[
+                (Action::Help, &["?"]),
+                (Action::CommandPalette, &[":"]),
+                (Action::CycleTheme, &["T"]),
+                (Action::Forward, &["]"]),
+                (Action::OpenLogs, &["L"]),
+            ]),
+            dashboard: KeyMap::new(&[
+                (Action::Quit, &["q", "esc"]),
+                (Action::Up, &["up"]),
+                (Action::Down, &["down"]),
+                (Action::Left, &["left"]),
+                (Action::Right, &["right"]),
+                (Action::NextPanel, &["tab"]),
+                (Action::PageUp, &["pageup"]),
+                (Action::PageDown, &["pagedown"]),
+                (Action::Home, &["home"]),
+                (Action::End, &["end"]),
+                (Action::Select, &["enter"]),
+                (Action::OpenTrader, &["p"]),
+                (Action::OpenWallet, &["w"]),
+                (Action::CycleWindow, &["t"]),
+                (Action::CycleSort, &["s"]),
+                (Action::ReverseSort, &["S"]),
+                (Action::EditFilter, &["f"]),
+                (Action::ClearFilter, &["F"]),
+                (Action::Export, &["e"]),
+                (Action::ToggleClosing, &["c"]),
+            ]),
+            detail: KeyMap::new(&[
+                (Action::Quit, &["q"]),
+                (Action::Back, &["esc", "backspace"]),
+                (Action::Up, &["up"]),
+                (Action::Down, &["down"]),
+                (Action::BuyYes, &["y"]),
+                (Action::BuyNo, &["n"]),
+                (Action::CycleRange, &["r"]),
+                (Action::NextPanel, &["c"]),
+                (Action::NextTab, &["tab"]),
+                (Action::PrevTab, &["backtab"]),
+                (Action::CloseTab, &["x"]),
+                (Action::ToggleSplit, &["v"]),
+                (Action::Left, &["left"]),
+                (Action::Right, &["right"]),
+            ]),
+            wallet: KeyMap::new(&[
+                (Action::Quit, &["q"]),
+                (Action::Back, &["esc", "backspace"]),
+                (Action::Approve, &["a"]),
+            ]),
+            trader: KeyMap::new(&[
+                (Action::Quit, &["q"]),
+                (Action::Back, &["esc", "backspace"]),
+                (Action::Up, &["up"]),
+                (Action::Down, &["down"]),
+                (Action::Select, &["enter"]),
+            ]),
+            logs: KeyMap::new(&[
+                (Action::Quit, &["q"]),
+                (Action::Back, &["esc", "backspace"]),
+                (Action::Up, &["up"]),
+                (Action::Down, &["down"]),
+                (Action::PageUp, &["pageup"]),
+                (Action::PageDown, &["pagedown"]),
+                (Action::Home, &["home"]),
+                (Action::End, &["end"]),
+            ]),
+        };
+
+        maps.global.apply(&config.global);
+        for (map, overrides) in [
+            (&mut maps.dashboard, &config.dashboard),
+            (&mut maps.detail, &config.detail),
+            (&mut maps.wallet, &config.wallet),
+            (&mut maps.trader, &config.trader),
+            (&mut maps.logs, &config.logs),
+        ] {
+            map.apply(&config.global);
+            map.apply(overrides);
+        }
+        maps
+    }
+
+    pub fn page(&self, page: PageType) -> &KeyMap {
+        match page {
+            PageType::Dashboard => &self.dashboard,
+            PageType::Detail => &self.detail,
+            PageType::Wallet => &self.wallet,
+            PageType::Trader => &self.trader,
+            PageType::Logs => &self.logs,
+        }
+    }
+}
+
+/// Builds the key maps from the loaded config, logging a warning for each binding
+/// that cannot be used. Called once at startup; `get` builds them on first use otherwise.
+pub fn init() -> &'static KeyMaps {
+    KEYMAPS.get_or_init(|| KeyMaps::load(&config::get().keys))
+}
+
+pub fn get() -> &'static KeyMaps {
+    init()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn binding(code: KeyCode, modifiers: KeyModifiers) -> Option<KeyBinding> {
+        Some(KeyBinding { code, modifiers })
+    }
+
+    #[test]
+    fn parses_characters_and_named_keys() {
+        assert_eq!(KeyBinding::parse("q"), binding(KeyCode::Char('q'), KeyModifiers::NONE));
+        assert_eq!(KeyBinding::parse("S"), binding(KeyCode::Char('S'), KeyModifiers::NONE));
+        assert_eq!(KeyBinding::parse("+"), binding(KeyCode::Char('+'), KeyModifiers::NONE));
+        assert_eq!(KeyBinding::parse("PageUp"), binding(KeyCode::PageUp, KeyModifiers::NONE));
+        assert_eq!(KeyBinding::parse("space"), binding(KeyCode::Char(' '), KeyModifiers::NONE));
+        assert_eq!(KeyBinding::parse("f12"), binding(KeyCode::F(12), KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn parses_modifiers() {
+        assert_eq!(KeyBinding::parse("ctrl+c"), binding(KeyCode::Char('c'), KeyModifiers::CONTROL));
+        assert_eq!(
+            KeyBinding::parse("Alt+Ctrl+left"),
+            binding(KeyCode::Left, KeyModifiers::ALT | KeyModifiers::CONTROL)
+        );
+        // A lone "+" after a modifier is the plus key itself.
+        assert_eq!(KeyBinding::parse("ctrl++"), binding(KeyCode::Char('+'), KeyModifiers::CONTROL));
+    }
+
+    #[test]
+    fn rejects_unknown_names() {
+        for input in ["", "ctrl+", "pgup", "fx", "ctrl+nope"] {
+            assert_eq!(KeyBinding::parse(input), None, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn matches_ignoring_shift() {
+        let binding = KeyBinding::parse("S").unwrap();
+        assert!(binding.matches(&KeyEvent::new(KeyCode::Char('S'), KeyModifiers::SHIFT)));
+        assert!(!binding.matches(&KeyEvent::new(KeyCode::Char('S'), KeyModifiers::CONTROL)));
+        assert_eq!(KeyBinding::parse("ctrl+pagedown").unwrap().label(), "Ctrl+PgDn");
+    }
+}
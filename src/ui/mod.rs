@@ -1,6 +1,8 @@
 mod app;
 mod pages;
 mod components;
+mod keymap;
+mod theme;
 mod runner;
 
 pub use runner::run;
\ No newline at end of file
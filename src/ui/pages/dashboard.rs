@@ -1,13 +1,24 @@
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::KeyEvent;
 use ratatui::{
     Frame,
-    layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    layout::{Constraint, Direction, Layout, Margin, Rect},
+    style::Style,
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Cell, List, ListItem, ListState, Paragraph, Row, Table, TableState},
 };
 
-use crate::data::state::{SharedState, MarketData};
+use crate::config;
+use crate::data::closing_markets::ClosingMarket;
+use crate::data::new_markets::NewMarket;
+use crate::data::prices::ACTIVITY_MINUTES;
+use crate::data::state::{now_secs, SharedState, MarketData, TimeWindow, Trade, TraderData};
+use crate::data::view::{ListSort, ListView, SortOrder};
+use crate::ui::components::filter_bar::FilterBar;
+use crate::ui::components::mouse::{Mouse, MouseKind};
+use crate::ui::components::format::{format_address, format_countdown, format_price, format_time, format_volume, sparkline};
+use crate::ui::components::scroll::ScrollState;
+use crate::ui::keymap::{self, Action};
+use crate::ui::theme;
 use super::{Page, PageAction};
 
 #[derive(Clone, Copy, PartialEq)]
@@ -16,15 +27,17 @@ pub enum SelectedBox {
     GeneralInfo,
     TopTraders,
     NewMarkets,
+    TradeTape,
 }
 
 impl SelectedBox {
     fn left(&self) -> Self {
         match self {
-            SelectedBox::TopMarkets => SelectedBox::NewMarkets,
+            SelectedBox::TopMarkets => SelectedBox::TradeTape,
             SelectedBox::GeneralInfo => SelectedBox::TopMarkets,
             SelectedBox::TopTraders => SelectedBox::GeneralInfo,
             SelectedBox::NewMarkets => SelectedBox::TopTraders,
+            SelectedBox::TradeTape => SelectedBox::NewMarkets,
         }
     }
 
@@ -33,7 +46,8 @@ impl SelectedBox {
             SelectedBox::TopMarkets => SelectedBox::GeneralInfo,
             SelectedBox::GeneralInfo => SelectedBox::TopTraders,
             SelectedBox::TopTraders => SelectedBox::NewMarkets,
-            SelectedBox::NewMarkets => SelectedBox::TopMarkets,
+            SelectedBox::NewMarkets => SelectedBox::TradeTape,
+            SelectedBox::TradeTape => SelectedBox::TopMarkets,
         }
     }
 
@@ -43,19 +57,36 @@ impl SelectedBox {
             SelectedBox::GeneralInfo => SelectedBox::NewMarkets,
             SelectedBox::TopTraders => SelectedBox::TopMarkets,
             SelectedBox::NewMarkets => SelectedBox::GeneralInfo,
+            SelectedBox::TradeTape => SelectedBox::TradeTape,
         }
     }
 
     fn down(&self) -> Self {
         self.up()
     }
+
+    /// Header lines between the panel's top border and its first row.
+    fn header_rows(&self) -> u16 {
+        match self {
+            SelectedBox::TopMarkets | SelectedBox::TradeTape => 1,
+            SelectedBox::GeneralInfo | SelectedBox::TopTraders | SelectedBox::NewMarkets => 0,
+        }
+    }
 }
 
+const MARKET_SORTS: &[ListSort] = &[ListSort::Volume, ListSort::Trades, ListSort::Change, ListSort::Recency, ListSort::Name];
+const TRADER_SORTS: &[ListSort] = &[ListSort::Volume, ListSort::Trades, ListSort::Name];
+const NEW_MARKET_SORTS: &[ListSort] = &[ListSort::Recency, ListSort::Volume, ListSort::Name];
+const CLOSING_SORTS: &[ListSort] = &[ListSort::Ending, ListSort::Volume, ListSort::Name];
+
 #[derive(Clone)]
 pub struct FrameData {
     pub top_markets: Vec<MarketData>,
-    pub top_traders: Vec<(String, f64)>,
-    pub new_markets: Vec<(String, String)>,
+    pub top_traders: Vec<TraderData>,
+    pub new_markets: Vec<NewMarket>,
+    pub closing_markets: Vec<ClosingMarket>,
+    pub tape: Vec<Trade>,
+    pub tape_threshold: f64,
     pub markets_updated_at: String,
     pub time_running: u64,
     pub total_markets: usize,
@@ -69,6 +100,9 @@ impl Default for FrameData {
             top_markets: vec![],
             top_traders: vec![],
             new_markets: vec![],
+            closing_markets: vec![],
+            tape: vec![],
+            tape_threshold: 0.0,
             markets_updated_at: "unknown".to_string(),
             time_running: 0,
             total_markets: 0,
@@ -80,53 +114,99 @@ impl Default for FrameData {
 
 pub struct DashboardPage {
     pub selected: SelectedBox,
-    pub top_markets_index: usize,
-    pub top_traders_index: usize,
-    pub new_markets_index: usize,
-    pub cached_frame_data: Option<FrameData>,
+    pub top_markets_scroll: ScrollState,
+    pub top_traders_scroll: ScrollState,
+    pub new_markets_scroll: ScrollState,
+    pub tape_scroll: ScrollState,
+    pub window: TimeWindow,
+    pub market_sort: SortOrder,
+    pub trader_sort: SortOrder,
+    pub new_market_sort: SortOrder,
+    pub closing_sort: SortOrder,
+    /// Whether the new markets panel lists markets closing soon instead.
+    pub show_closing: bool,
+    pub filter_bar: FilterBar,
+    /// Where each panel was drawn last frame, for mouse hit-testing.
+    panel_areas: Vec<(SelectedBox, Rect)>,
+    filter_area: Option<Rect>,
 }
 
 impl DashboardPage {
     pub fn new() -> Self {
         Self {
             selected: SelectedBox::TopMarkets,
-            top_markets_index: 0,
-            top_traders_index: 0,
-            new_markets_index: 0,
-            cached_frame_data: None,
+            top_markets_scroll: ScrollState::default(),
+            top_traders_scroll: ScrollState::default(),
+            new_markets_scroll: ScrollState::default(),
+            tape_scroll: ScrollState::default(),
+            window: TimeWindow::Session,
+            market_sort: SortOrder::new(ListSort::Volume),
+            trader_sort: SortOrder::new(ListSort::Volume),
+            new_market_sort: SortOrder::new(ListSort::Recency),
+            closing_sort: SortOrder::new(ListSort::Ending),
+            show_closing: false,
+            filter_bar: FilterBar::default(),
+            panel_areas: vec![],
+            filter_area: None,
         }
     }
 
-    fn prepare_frame_data(&mut self, state: &SharedState) -> FrameData {
-        match state.try_lock() {
-            Ok(mut app_state) => {
-                let top_markets = app_state.get_top_markets();
-                let traders = app_state.get_top_traders();
-                let new_markets = app_state.new_markets().clone();
-                let markets_updated_at = app_state.last_updated_markets();
-                let general_data = app_state.general_stats();
-
-                let data = FrameData {
-                    top_markets: top_markets.0,
-                    top_traders: traders,
-                    new_markets,
-                    markets_updated_at,
-                    time_running: general_data.1,
-                    total_markets: general_data.0,
-                    total_trades: general_data.2,
-                    total_volume: general_data.3,
-                };
-                self.cached_frame_data = Some(data.clone());
-                data
-            }
-            Err(_) => self.cached_frame_data.clone().unwrap_or_default(),
+    /// What the state actor should publish for Top Markets and Top Traders.
+    pub fn view(&self) -> ListView {
+        ListView {
+            window: self.window,
+            filter: self.filter_bar.filter(),
+            market_sort: self.market_sort,
+            trader_sort: self.trader_sort,
+        }
+    }
+
+    fn prepare_frame_data(&self, state: &SharedState) -> FrameData {
+        let snapshot = state.borrow().clone();
+        let window = snapshot.window(self.window);
+        let filter = self.filter_bar.filter();
+        let now = now_secs();
+
+        // Top markets and traders come filtered and sorted by the state actor, which
+        // applies `view()` to the full leaderboards.
+        let top_markets = snapshot.view.markets.clone();
+        let top_traders = snapshot.view.traders.clone();
+
+        let mut new_markets: Vec<NewMarket> = snapshot
+            .new_markets
+            .iter()
+            .filter(|m| filter.matches_market(&m.question, m.volume, Some(&m.meta), now))
+            .cloned()
+            .collect();
+        self.new_market_sort.key.sort_new_markets(&mut new_markets, self.new_market_sort.descending);
+
+        let mut closing_markets: Vec<ClosingMarket> = snapshot
+            .closing_markets
+            .iter()
+            .filter(|m| m.end_date >= now && filter.matches_market(&m.question, m.volume, Some(&m.meta), now))
+            .cloned()
+            .collect();
+        self.closing_sort.key.sort_closing_markets(&mut closing_markets, self.closing_sort.descending);
+
+        FrameData {
+            top_markets,
+            top_traders,
+            new_markets,
+            closing_markets,
+            tape: snapshot.tape.clone(),
+            tape_threshold: snapshot.tape_threshold,
+            markets_updated_at: snapshot.last_updated_markets(),
+            time_running: snapshot.time_running(),
+            total_markets: window.total_markets,
+            total_trades: window.total_trades,
+            total_volume: window.total_volume,
         }
     }
 
     fn get_selected_item_info(&self, frame_data: &FrameData) -> Option<(String, String, String)> {
         match self.selected {
             SelectedBox::TopMarkets => {
-                frame_data.top_markets.get(self.top_markets_index).map(|m| {
+                frame_data.top_markets.get(self.top_markets_scroll.selected).map(|m| {
                     (
                         format!("Market: {}", m.name),
                         format!(
@@ -138,48 +218,295 @@ impl DashboardPage {
                     )
                 })
             }
-            SelectedBox::TopTraders => {
-                frame_data.top_traders.get(self.top_traders_index).map(|(addr, vol)| {
+            SelectedBox::NewMarkets if self.show_closing => {
+                frame_data.closing_markets.get(self.new_markets_scroll.selected).map(|m| {
                     (
-                        format!("Trader: {}", format_address(addr)),
+                        format!("Closing Market: {}", m.question),
                         format!(
-                            "Address: {}\nVolume: {}\n\n[More details will go here]",
-                            addr,
-                            format_volume(*vol)
+                            "Name: {}\nVolume: {}\nEnds in: {}",
+                            m.question,
+                            format_volume(m.volume),
+                            format_countdown(m.end_date.saturating_sub(now_secs()))
                         ),
-                        String::from(addr)
-
+                        m.slug.clone()
                     )
                 })
             }
             SelectedBox::NewMarkets => {
-                frame_data.new_markets.get(self.new_markets_index).map(|(name, vol)| {
+                frame_data.new_markets.get(self.new_markets_scroll.selected).map(|m| {
                     (
-                        format!("New Market: {}", name),
+                        format!("New Market: {}", m.question),
                         format!(
                             "Name: {}\nVolume: {}\n\n[More details will go here]",
-                            name, vol
+                            m.question,
+                            format_volume(m.volume)
                         ),
-                        String::from("temp")
-
+                        m.slug.clone()
                     )
                 })
             }
+            SelectedBox::TradeTape => {
+                frame_data.tape.get(self.tape_scroll.selected).map(|t| {
+                    (
+                        format!("Market: {}", t.title),
+                        format!(
+                            "Name: {}\nLast tape trade: {} {} @ {:.2} for ${}",
+                            t.title,
+                            t.side,
+                            t.outcome,
+                            t.price,
+                            format_volume(t.notional())
+                        ),
+                        t.slug.clone()
+                    )
+                })
+            }
+            SelectedBox::TopTraders | SelectedBox::GeneralInfo => None,
+        }
+    }
+
+    fn get_selected_trader(&self, frame_data: &FrameData) -> Option<String> {
+        match self.selected {
+            SelectedBox::TopTraders => frame_data.top_traders.get(self.top_traders_scroll.selected).map(|t| t.address.clone()),
+            SelectedBox::TradeTape => frame_data.tape.get(self.tape_scroll.selected).map(|t| t.trader.clone()),
+            _ => None,
+        }
+    }
+
+    /// The focused panel's scroll state and item count, if it is a list.
+    fn selected_list(&mut self, frame_data: &FrameData) -> Option<(&mut ScrollState, usize)> {
+        match self.selected {
+            SelectedBox::TopMarkets => Some((&mut self.top_markets_scroll, frame_data.top_markets.len())),
+            SelectedBox::TopTraders => Some((&mut self.top_traders_scroll, frame_data.top_traders.len())),
+            SelectedBox::NewMarkets if self.show_closing => {
+                Some((&mut self.new_markets_scroll, frame_data.closing_markets.len()))
+            }
+            SelectedBox::NewMarkets => Some((&mut self.new_markets_scroll, frame_data.new_markets.len())),
+            SelectedBox::TradeTape => Some((&mut self.tape_scroll, frame_data.tape.len())),
             SelectedBox::GeneralInfo => None,
         }
     }
+
+    /// The focused list's sort order and the keys it can be sorted by.
+    fn selected_sort(&mut self) -> Option<(&mut SortOrder, &'static [ListSort])> {
+        match self.selected {
+            SelectedBox::TopMarkets => Some((&mut self.market_sort, MARKET_SORTS)),
+            SelectedBox::TopTraders => Some((&mut self.trader_sort, TRADER_SORTS)),
+            SelectedBox::NewMarkets if self.show_closing => Some((&mut self.closing_sort, CLOSING_SORTS)),
+            SelectedBox::NewMarkets => Some((&mut self.new_market_sort, NEW_MARKET_SORTS)),
+            SelectedBox::GeneralInfo | SelectedBox::TradeTape => None,
+        }
+    }
+
+    /// Moves the filtered lists back to their first item.
+    fn reset_scroll(&mut self) {
+        self.top_markets_scroll.home();
+        self.top_traders_scroll.home();
+        self.new_markets_scroll.home();
+    }
+
+    fn render_top_markets(&mut self, frame: &mut Frame, area: Rect, frame_data: &FrameData, border_style: Style) {
+        // Borders and the header row take three lines.
+        let theme = theme::current();
+        let range = self.top_markets_scroll.visible(frame_data.top_markets.len(), area.height.saturating_sub(3) as usize);
+        let rows: Vec<Row> = frame_data.top_markets[range.clone()]
+            .iter()
+            .zip(range)
+            .map(|(m, rank)| {
+                let change_color = match m.price.as_ref().map(|p| p.change()) {
+                    Some(c) if c != 0.0 => theme.direction(c > 0.0),
+                    _ => theme.muted,
+                };
+                Row::new(vec![
+                    Cell::from(format!("{}", rank + 1)),
+                    Cell::from(m.name.clone()),
+                    Cell::from(format_volume(m.volume)),
+                    Cell::from(m.trades.to_string()),
+                    Cell::from(Span::styled(
+                        m.price.as_ref().map(format_price).unwrap_or_default(),
+                        Style::default().fg(change_color),
+                    )),
+                    Cell::from(Span::styled(sparkline(&m.activity), Style::default().fg(theme.accent))),
+                ])
+            })
+            .collect();
+
+        let widths = [
+            Constraint::Length(3),
+            Constraint::Fill(1),
+            Constraint::Length(8),
+            Constraint::Length(6),
+            Constraint::Length(16),
+            Constraint::Length(ACTIVITY_MINUTES as u16),
+        ];
+
+        let header_cells = [
+            ("#", None),
+            ("Market", Some(ListSort::Name)),
+            ("Volume", Some(ListSort::Volume)),
+            ("Trades", Some(ListSort::Trades)),
+            ("Last", Some(ListSort::Change)),
+            ("Activity", Some(ListSort::Recency)),
+        ]
+        .map(|(label, sort)| {
+            if sort == Some(self.market_sort.key) {
+                Cell::from(format!("{}{}", label, self.market_sort.arrow()))
+            } else {
+                Cell::from(label)
+            }
+        });
+        let header = Row::new(header_cells).style(theme.muted());
+
+        let block = Block::default()
+            .title(format!(
+                "Top Markets ({})",
+                self.window.label()
+            ))
+            .title_bottom(self.top_markets_scroll.position(frame_data.top_markets.len()))
+            .borders(Borders::ALL)
+            .border_style(border_style);
+
+        let mut table_state = TableState::default();
+        if self.selected == SelectedBox::TopMarkets {
+            table_state.select(Some(self.top_markets_scroll.relative()));
+        }
+        frame.render_stateful_widget(
+            Table::new(rows, widths)
+                .header(header)
+                .block(block)
+                .row_highlight_style(theme.highlight()),
+            area,
+            &mut table_state,
+        );
+    }
+
+    fn render_tape(&mut self, frame: &mut Frame, area: Rect, frame_data: &FrameData, border_style: Style) {
+        let theme = theme::current();
+        let range = self.tape_scroll.visible(frame_data.tape.len(), area.height.saturating_sub(3) as usize);
+        let rows: Vec<Row> = frame_data.tape[range]
+            .iter()
+            .map(|t| {
+                let side_color = theme.direction(t.is_buy());
+                let trader = t.trader_name.clone().unwrap_or_else(|| format_address(&t.trader));
+                Row::new(vec![
+                    Cell::from(format_time(t.timestamp)),
+                    Cell::from(t.title.clone()),
+                    Cell::from(Span::styled(t.side.clone(), Style::default().fg(side_color))),
+                    Cell::from(t.outcome.clone()),
+                    Cell::from(format!("{:.2}", t.price)),
+                    Cell::from(format_volume(t.size)),
+                    Cell::from(trader),
+                ])
+            })
+            .collect();
+
+        let widths = [
+            Constraint::Length(8),
+            Constraint::Fill(1),
+            Constraint::Length(4),
+            Constraint::Length(7),
+            Constraint::Length(5),
+            Constraint::Length(8),
+            Constraint::Length(13),
+        ];
+
+        let header = Row::new(vec!["Time", "Market", "Side", "Outcome", "Price", "Size", "Trader"])
+            .style(theme.muted());
+
+        let block = Block::default()
+            .title(format!(
+                "Trade Tape >= ${}",
+                format_volume(frame_data.tape_threshold)
+            ))
+            .title_bottom(self.tape_scroll.position(frame_data.tape.len()))
+            .borders(Borders::ALL)
+            .border_style(border_style);
+
+        let mut table_state = TableState::default();
+        if self.selected == SelectedBox::TradeTape {
+            table_state.select(Some(self.tape_scroll.relative()));
+        }
+        frame.render_stateful_widget(
+            Table::new(rows, widths)
+                .header(header)
+                .block(block)
+                .row_highlight_style(theme.highlight()),
+            area,
+            &mut table_state,
+        );
+    }
+
+    fn render_list(
+        frame: &mut Frame,
+        area: Rect,
+        block: Block,
+        items: Vec<ListItem>,
+        scroll: &ScrollState,
+        focused: bool,
+    ) {
+        let mut list_state = ListState::default();
+        if focused {
+            list_state.select(Some(scroll.relative()));
+        }
+        frame.render_stateful_widget(
+            List::new(items)
+                .block(block)
+                .highlight_style(theme::current().highlight()),
+            area,
+            &mut list_state,
+        );
+    }
 }
 
 impl Page for DashboardPage {
     fn render(&mut self, frame: &mut Frame, area: Rect, state: &SharedState) {
         let frame_data = self.prepare_frame_data(state);
 
-        let chunks = Layout::default()
+        let rows = Layout::default()
             .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(1)])
+            .split(area);
+        let help_text = keymap::get().dashboard.help_line(&[
+            (Action::NextPanel, "Switch Panel"),
+            (Action::Select, "Open"),
+            (Action::OpenTrader, "Trader"),
+            (Action::CycleWindow, "Window"),
+            (Action::CycleSort, "Sort"),
+            (Action::ReverseSort, "Reverse"),
+            (Action::EditFilter, "Filter"),
+            (Action::ToggleClosing, "Closing Soon"),
+            (Action::OpenWallet, "Wallet"),
+            (Action::Export, "Export"),
+            (Action::Quit, "Quit"),
+        ]);
+        frame.render_widget(Paragraph::new(help_text), rows[1].inner(Margin::new(2, 0)));
+        let area = rows[0];
+
+        self.filter_area = None;
+        let area = if self.filter_bar.is_visible() {
+            let rows = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(3), Constraint::Min(0)])
+                .split(area);
+            let filter_area = rows[0].inner(Margin::new(2, 0));
+            self.filter_bar.render(frame, filter_area);
+            self.filter_area = Some(filter_area);
+            rows[1]
+        } else {
+            area
+        };
+
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
             .margin(1)
-            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)].as_ref())
             .split(area);
 
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+            .split(columns[0]);
+
         let top_chunks = Layout::default()
             .direction(Direction::Horizontal)
             .margin(1)
@@ -192,44 +519,34 @@ impl Page for DashboardPage {
             .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
             .split(chunks[1]);
 
-        let selected_border_style = Style::default().fg(Color::Yellow);
-        let normal_border_style = Style::default();
+        let tape_area = columns[1].inner(Margin::new(1, 1));
+        self.panel_areas = vec![
+            (SelectedBox::TopMarkets, top_chunks[0]),
+            (SelectedBox::GeneralInfo, top_chunks[1]),
+            (SelectedBox::TopTraders, bottom_chunks[0]),
+            (SelectedBox::NewMarkets, bottom_chunks[1]),
+            (SelectedBox::TradeTape, tape_area),
+        ];
 
+        let selected_border_style = theme::current().border(true);
+        let normal_border_style = theme::current().border(false);
 
-        let top_markets_items: Vec<Line> = frame_data
-            .top_markets
-            .iter()
-            .enumerate()
-            .map(|(rank, m)| {
-                let text = format!("{}. {} - {}", rank + 1, m.name, format_volume(m.volume));
-                if self.selected == SelectedBox::TopMarkets && rank == self.top_markets_index {
-                    Line::from(Span::styled(
-                        text,
-                        Style::default().bg(Color::Blue).add_modifier(Modifier::BOLD),
-                    ))
-                } else {
-                    Line::from(text)
-                }
-            })
-            .collect();
 
-        let top_markets_block = Block::default()
-            .title("Top Markets [↑/↓ select, Enter open, Tab switch]")
-            .borders(Borders::ALL)
-            .border_style(if self.selected == SelectedBox::TopMarkets {
+        self.render_top_markets(
+            frame,
+            top_chunks[0],
+            &frame_data,
+            if self.selected == SelectedBox::TopMarkets {
                 selected_border_style
             } else {
                 normal_border_style
-            });
-
-        frame.render_widget(
-            Paragraph::new(top_markets_items).block(top_markets_block),
-            top_chunks[0],
+            },
         );
 
         let general_info_text = format!(
-            "Running for: {} seconds\nTotal trades tracked: {}\nTotal markets discovered: {}\nTotal volume: ${}",
+            "Running for: {} seconds\nWindow: {}\nTotal trades tracked: {}\nTotal markets discovered: {}\nTotal volume: ${}",
             frame_data.time_running,
+            self.window.label(),
             frame_data.total_trades,
             frame_data.total_markets,
             format_volume(frame_data.total_volume),
@@ -249,25 +566,19 @@ impl Page for DashboardPage {
             top_chunks[1],
         );
 
-        let top_traders_items: Vec<Line> = frame_data
-            .top_traders
+        let traders_area = bottom_chunks[0];
+        let range = self.top_traders_scroll.visible(frame_data.top_traders.len(), traders_area.height.saturating_sub(2) as usize);
+        let top_traders_items: Vec<ListItem> = frame_data.top_traders[range.clone()]
             .iter()
-            .enumerate()
-            .map(|(rank, (addr, vol))| {
-                let text = format!("{}. {} - {}", rank + 1, format_address(addr), format_volume(*vol));
-                if self.selected == SelectedBox::TopTraders && rank == self.top_traders_index {
-                    Line::from(Span::styled(
-                        text,
-                        Style::default().bg(Color::Blue).add_modifier(Modifier::BOLD),
-                    ))
-                } else {
-                    Line::from(text)
-                }
+            .zip(range)
+            .map(|(t, rank)| {
+                ListItem::new(format!("{}. {} - {}", rank + 1, format_address(&t.address), format_volume(t.volume)))
             })
             .collect();
 
         let traders_block = Block::default()
-            .title("Top Traders")
+            .title(format!("Top Traders ({}, by {})", self.window.label(), self.trader_sort.label()))
+            .title_bottom(self.top_traders_scroll.position(frame_data.top_traders.len()))
             .borders(Borders::ALL)
             .border_style(if self.selected == SelectedBox::TopTraders {
                 selected_border_style
@@ -275,32 +586,55 @@ impl Page for DashboardPage {
                 normal_border_style
             });
 
-        frame.render_widget(
-            Paragraph::new(top_traders_items).block(traders_block),
-            bottom_chunks[0],
+        Self::render_list(
+            frame,
+            traders_area,
+            traders_block,
+            top_traders_items,
+            &self.top_traders_scroll,
+            self.selected == SelectedBox::TopTraders,
         );
-        let new_markets_items: Vec<Line> = frame_data
-            .new_markets
-            .iter()
-            .enumerate()
-            .map(|(idx, (name, volume))| {
-                let text = format!("{} - {}", name, volume);
-                if self.selected == SelectedBox::NewMarkets && idx == self.new_markets_index {
-                    Line::from(Span::styled(
-                        text,
-                        Style::default().bg(Color::Blue).add_modifier(Modifier::BOLD),
-                    ))
-                } else {
-                    Line::from(text)
-                }
-            })
-            .collect();
 
-        let new_markets_block = Block::default()
-            .title(format!(
-                "New Markets - Last updated {} ago",
+        let new_markets_area = bottom_chunks[1];
+        let height = new_markets_area.height.saturating_sub(2) as usize;
+        let (new_markets_items, title, len): (Vec<ListItem>, String, usize) = if self.show_closing {
+            let len = frame_data.closing_markets.len();
+            let now = now_secs();
+            let items = frame_data.closing_markets[self.new_markets_scroll.visible(len, height)]
+                .iter()
+                .map(|m| {
+                    ListItem::new(Line::from(vec![
+                        Span::styled(
+                            format!("{:>7} ", format_countdown(m.end_date.saturating_sub(now))),
+                            Style::default().fg(theme::current().accent),
+                        ),
+                        Span::raw(format!("{} - {}", m.question, format_volume(m.volume))),
+                    ]))
+                })
+                .collect();
+            let title = format!(
+                "Closing Soon (within {}h, by {})",
+                config::get().closing_horizon_hours,
+                self.closing_sort.label()
+            );
+            (items, title, len)
+        } else {
+            let len = frame_data.new_markets.len();
+            let items = frame_data.new_markets[self.new_markets_scroll.visible(len, height)]
+                .iter()
+                .map(|m| ListItem::new(format!("{} - {}", m.question, format_volume(m.volume))))
+                .collect();
+            let title = format!(
+                "New Markets (by {}) - Last updated {} ago",
+                self.new_market_sort.label(),
                 frame_data.markets_updated_at
-            ))
+            );
+            (items, title, len)
+        };
+
+        let new_markets_block = Block::default()
+            .title(title)
+            .title_bottom(self.new_markets_scroll.position(len))
             .borders(Borders::ALL)
             .border_style(if self.selected == SelectedBox::NewMarkets {
                 selected_border_style
@@ -308,113 +642,192 @@ impl Page for DashboardPage {
                 normal_border_style
             });
 
-        frame.render_widget(
-            Paragraph::new(new_markets_items).block(new_markets_block),
-            bottom_chunks[1],
+        Self::render_list(
+            frame,
+            new_markets_area,
+            new_markets_block,
+            new_markets_items,
+            &self.new_markets_scroll,
+            self.selected == SelectedBox::NewMarkets,
+        );
+
+        self.render_tape(
+            frame,
+            tape_area,
+            &frame_data,
+            if self.selected == SelectedBox::TradeTape {
+                selected_border_style
+            } else {
+                normal_border_style
+            },
         );
     }
 
     fn handle_input(&mut self, key: KeyEvent, state: &SharedState) -> PageAction {
+        if self.filter_bar.editing {
+            if self.filter_bar.handle_key(key) {
+                self.reset_scroll();
+            }
+            return PageAction::None;
+        }
+
+        match keymap::get().dashboard.action(&key) {
+            Some(action) => self.handle_action(action, state),
+            None => PageAction::None,
+        }
+    }
+
+    fn handle_action(&mut self, action: Action, state: &SharedState) -> PageAction {
         let frame_data = self.prepare_frame_data(state);
 
-        match key.code {
-            KeyCode::Char('q') => PageAction::Quit,
-            KeyCode::Esc => PageAction::Quit,
-            KeyCode::Left => {
+        match action {
+            Action::Quit => PageAction::Quit,
+            Action::Left => {
                 self.selected = self.selected.left();
                 PageAction::None
             }
-            KeyCode::Right => {
+            Action::Right => {
                 self.selected = self.selected.right();
                 PageAction::None
             }
-            KeyCode::Up => {
-                match self.selected {
-                    SelectedBox::TopMarkets => {
-                        if self.top_markets_index > 0 {
-                            self.top_markets_index -= 1;
-                        }
-                    }
-                    SelectedBox::TopTraders => {
-                        if self.top_traders_index > 0 {
-                            self.top_traders_index -= 1;
-                        }
-                    }
-                    SelectedBox::NewMarkets => {
-                        if self.new_markets_index > 0 {
-                            self.new_markets_index -= 1;
-                        }
-                    }
-                    SelectedBox::GeneralInfo => {
-                        self.selected = self.selected.up();
-                    }
+            Action::Up => {
+                match self.selected_list(&frame_data) {
+                    Some((scroll, _)) => scroll.up(),
+                    None => self.selected = self.selected.up(),
+                }
+                PageAction::None
+            }
+            Action::Down => {
+                match self.selected_list(&frame_data) {
+                    Some((scroll, len)) => scroll.down(len),
+                    None => self.selected = self.selected.down(),
+                }
+                PageAction::None
+            }
+            Action::PageUp => {
+                if let Some((scroll, _)) = self.selected_list(&frame_data) {
+                    scroll.page_up();
+                }
+                PageAction::None
+            }
+            Action::PageDown => {
+                if let Some((scroll, len)) = self.selected_list(&frame_data) {
+                    scroll.page_down(len);
+                }
+                PageAction::None
+            }
+            Action::Home => {
+                if let Some((scroll, _)) = self.selected_list(&frame_data) {
+                    scroll.home();
                 }
                 PageAction::None
             }
-            KeyCode::Down => {
-                match self.selected {
-                    SelectedBox::TopMarkets => {
-                        if self.top_markets_index < frame_data.top_markets.len().saturating_sub(1) {
-                            self.top_markets_index += 1;
-                        }
-                    }
-                    SelectedBox::TopTraders => {
-                        if self.top_traders_index < frame_data.top_traders.len().saturating_sub(1) {
-                            self.top_traders_index += 1;
-                        }
-                    }
-                    SelectedBox::NewMarkets => {
-                        if self.new_markets_index < frame_data.new_markets.len().saturating_sub(1) {
-                            self.new_markets_index += 1;
-                        }
-                    }
-                    SelectedBox::GeneralInfo => {
-                        self.selected = self.selected.down();
-                    }
+            Action::End => {
+                if let Some((scroll, len)) = self.selected_list(&frame_data) {
+                    scroll.end(len);
                 }
                 PageAction::None
             }
-            KeyCode::Enter => {
+            Action::Select => {
                 if let Some((title, content, id)) = self.get_selected_item_info(&frame_data) {
                     PageAction::NavigateToDetail { title, content, identifier: id }
+                } else if let (SelectedBox::TopTraders, Some(address)) = (self.selected, self.get_selected_trader(&frame_data)) {
+                    PageAction::NavigateToTrader { address }
                 } else {
                     PageAction::None
                 }
             }
-            KeyCode::Tab => {
-                self.selected = match self.selected {
-                    SelectedBox::TopMarkets => SelectedBox::GeneralInfo,
-                    SelectedBox::GeneralInfo => SelectedBox::TopTraders,
-                    SelectedBox::TopTraders => SelectedBox::NewMarkets,
-                    SelectedBox::NewMarkets => SelectedBox::TopMarkets,
-                };
+            Action::OpenTrader => {
+                if let Some(address) = self.get_selected_trader(&frame_data) {
+                    PageAction::NavigateToTrader { address }
+                } else {
+                    PageAction::None
+                }
+            }
+            Action::NextPanel => {
+                self.selected = self.selected.right();
+                PageAction::None
+            }
+            Action::CycleWindow => {
+                self.window = self.window.next();
+                self.top_markets_scroll.home();
+                self.top_traders_scroll.home();
                 PageAction::None
             }
-            KeyCode::Char('w') => {
+            Action::CycleSort => {
+                if let Some((sort, keys)) = self.selected_sort() {
+                    sort.next(keys);
+                }
+                PageAction::None
+            }
+            Action::ReverseSort => {
+                if let Some((sort, _)) = self.selected_sort() {
+                    sort.descending = !sort.descending;
+                }
+                PageAction::None
+            }
+            Action::EditFilter => {
+                self.filter_bar.open();
+                PageAction::None
+            }
+            Action::ClearFilter => {
+                self.filter_bar.clear();
+                self.reset_scroll();
+                PageAction::None
+            }
+            Action::OpenWallet => {
                 PageAction::NavigateToWallet {title: String::from("hey")}
             }
+            Action::Export => PageAction::Export { window: self.window },
+            Action::ToggleClosing => {
+                self.show_closing = !self.show_closing;
+                self.new_markets_scroll.home();
+                PageAction::None
+            }
             _ => PageAction::None,
         }
     }
 
-}
+    fn handle_mouse(&mut self, mouse: Mouse, state: &SharedState) -> PageAction {
+        if let Some(area) = self.filter_area
+            && mouse.is_in(area)
+        {
+            if mouse.is_click() {
+                self.filter_bar.open();
+            }
+            return PageAction::None;
+        }
+        self.filter_bar.editing = false;
 
-fn format_volume(volume: f64) -> String {
-    if volume >= 1_000_000_000.0 {
-        format!("{:.2}B", volume / 1_000_000_000.0)
-    } else if volume >= 1_000_000.0 {
-        format!("{:.2}M", volume / 1_000_000.0)
-    } else if volume >= 1_000.0 {
-        format!("{:.2}K", volume / 1_000.0)
-    } else {
-        format!("{:.2}", volume)
+        let Some((panel, area)) = self.panel_areas.iter().copied().find(|(_, area)| mouse.is_in(*area)) else {
+            return PageAction::None;
+        };
+        self.selected = panel;
+        let frame_data = self.prepare_frame_data(state);
+        let Some((scroll, len)) = self.selected_list(&frame_data) else {
+            return PageAction::None;
+        };
+
+        match mouse.kind {
+            MouseKind::ScrollUp => scroll.up(),
+            MouseKind::ScrollDown => scroll.down(len),
+            MouseKind::Click => {
+                if let Some(row) = mouse.row_in(area, panel.header_rows()) {
+                    scroll.select_visible(row, len);
+                }
+            }
+            MouseKind::DoubleClick => {
+                if let Some(row) = mouse.row_in(area, panel.header_rows())
+                    && scroll.select_visible(row, len)
+                {
+                    return self.handle_action(Action::Select, state);
+                }
+            }
+        }
+        PageAction::None
     }
-}
 
-fn format_address(address: &str) -> String {
-    if address.len() <= 10 {
-        address.to_string()
-    } else {
-        format!("{}...{}", &address[..6], &address[address.len() - 4..])
+    fn is_capturing_input(&self) -> bool {
+        self.filter_bar.editing
     }
 }
\ No newline at end of file
@@ -1,19 +1,32 @@
+use std::collections::HashSet;
 use std::env;
 use std::time::{Duration, Instant};
 use crossterm::event::{KeyCode, KeyEvent};
-use crossterm::style::Stylize;
+use tokio::sync::mpsc;
 use dotenv::dotenv;
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
+    symbols::Marker,
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Wrap, Clear},
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph, Wrap, Clear},
 };
+use crate::data::comments::get_comments;
+use crate::data::feed::Comment;
 use crate::data::get_market::get_market_from_slug;
-use crate::data::state::SharedState;
+use crate::data::market_meta::parse_timestamp;
+use crate::data::order_book::OrderBookFeed;
+use crate::data::pipeline::{event_comments, market_prices, market_trades, EventSender};
+use crate::data::price_history::{get_price_history, HistoryRange};
+use crate::data::prices::MarketPrices;
+use crate::data::state::{now_secs, LiveComments, SharedState, Trade};
 use crate::data::types::MarketSpecificDetails;
 use crate::actions::buy::buy_yes;
+use crate::ui::components::mouse::{Mouse, MouseKind};
+use crate::ui::components::format::{format_address, format_change, format_countdown, format_date_time, format_time, format_volume};
+use crate::ui::keymap::{self, Action};
+use crate::ui::theme;
 use super::{Page, PageAction};
 
 #[derive(PartialEq, Clone)]
@@ -29,6 +42,24 @@ pub struct DetailPage {
     pub scroll_offset: u16,
     pub id: String,
     pub market_data: Option<MarketSpecificDetails>,
+    pub live_prices: Option<MarketPrices>,
+    pub live_trades: Vec<Trade>,
+    pub history_range: HistoryRange,
+    pub history: Vec<(String, Vec<(f64, f64)>)>,
+    pub history_error: Option<String>,
+    pub history_fetched: Option<Instant>,
+    pub hidden_outcomes: HashSet<usize>,
+    /// Comments on the market's event, newest first: the last fetch merged with the feed.
+    pub comments: Vec<Comment>,
+    fetched_comments: Vec<Comment>,
+    live_comments: LiveComments,
+    pub comments_error: Option<String>,
+    pub comments_fetched: Option<Instant>,
+    pub comments_scroll: u16,
+    /// Whether Up/Down scroll the comments instead of the details.
+    pub comments_focused: bool,
+    /// Where the comments were drawn last frame, for mouse scrolling.
+    comments_area: Rect,
     pub last_fetch: Option<Instant>,
     pub is_loading: bool,
     pub error: Option<String>,
@@ -38,6 +69,15 @@ pub struct DetailPage {
     pub buy_resp: String,
     pub input_mode: InputMode,
     pub input_buffer: String,
+    /// Buy dialog buttons as drawn last frame, for mouse hit-testing.
+    confirm_button: Rect,
+    cancel_button: Rect,
+    /// Started on the first `refresh`.
+    task: Option<RefreshTask>,
+    /// Live book for the market's outcome tokens while it trades, closed with the tab.
+    order_book: Option<OrderBookFeed>,
+    history_loading: bool,
+    comments_loading: bool,
 }
 
 impl DetailPage {
@@ -50,6 +90,21 @@ impl DetailPage {
             scroll_offset: 0,
             id: identifier,
             market_data: None,
+            live_prices: None,
+            live_trades: vec![],
+            history_range: HistoryRange::OneDay,
+            history: vec![],
+            history_error: None,
+            history_fetched: None,
+            hidden_outcomes: HashSet::new(),
+            comments: vec![],
+            fetched_comments: vec![],
+            live_comments: LiveComments::default(),
+            comments_error: None,
+            comments_fetched: None,
+            comments_scroll: 0,
+            comments_focused: false,
+            comments_area: Rect::default(),
             last_fetch: None,
             is_loading: false,
             error: None,
@@ -59,78 +114,499 @@ impl DetailPage {
             buy_resp: "".to_string(),
             input_mode: InputMode::Normal,
             input_buffer: String::new(),
+            confirm_button: Rect::default(),
+            cancel_button: Rect::default(),
+            task: None,
+            order_book: None,
+            history_loading: false,
+            comments_loading: false,
         }
     }
 
-    pub fn should_refresh(&self) -> bool {
-        match self.last_fetch {
-            None => true,
-            Some(last) => last.elapsed() >= Duration::from_secs(2),
+    /// Applies whatever the tab's refresh task has finished, then hands it the requests
+    /// that are due. Never waits on the network, so the UI loop can call it every frame.
+    pub fn refresh(&mut self, events: &EventSender) {
+        let task = self
+            .task
+            .get_or_insert_with(|| RefreshTask::spawn(self.id.clone(), self.private_key.clone(), events.clone()));
+        let updates: Vec<Update> = std::iter::from_fn(|| task.updates.try_recv().ok()).collect();
+        for update in updates {
+            self.apply(update);
+        }
+        self.sync_order_book();
+
+        if self.should_refresh() {
+            self.is_loading = true;
+            self.error = None;
+            let event_id = self.market_data.as_ref().and_then(|d| d.event_id());
+            self.request(Request::Market { event_id });
+        }
+        if self.should_refresh_history()
+            && let Some(data) = &self.market_data
+        {
+            let request = Request::History {
+                outcomes: data.outcome_names(),
+                tokens: data.token_ids(),
+                range: self.history_range,
+            };
+            self.history_loading = true;
+            self.request(request);
+        }
+        if self.should_refresh_comments()
+            && let Some(event_id) = self.market_data.as_ref().and_then(|d| d.event_id())
+        {
+            self.comments_loading = true;
+            self.request(Request::Comments { event_id });
+        }
+        if self.buy_yes || self.buy_no {
+            let yes = self.buy_yes;
+            self.buy_yes = false;
+            self.buy_no = false;
+            if let Ok(amount) = self.input_buffer.parse() {
+                self.buy(yes, amount);
+            }
         }
     }
 
-    pub async fn fetch_market_data(&mut self) {
-        self.is_loading = true;
-        self.error = None;
+    /// Subscribes to the book once the market's tokens are known, and drops the
+    /// subscription when the market stops trading.
+    fn sync_order_book(&mut self) {
+        let tokens = self
+            .market_data
+            .as_ref()
+            .filter(|d| d.is_tradable())
+            .map(|d| d.token_ids())
+            .unwrap_or_default();
+        if tokens.is_empty() {
+            self.order_book = None;
+        } else if self.order_book.as_ref().is_none_or(|feed| feed.token_ids != tokens) {
+            self.order_book = Some(OrderBookFeed::subscribe(tokens));
+        }
+    }
 
-        match get_market_from_slug(&self.id).await {
-            Ok(data) => {
-                self.market_data = Some(data);
+    fn request(&self, request: Request) {
+        if let Some(task) = &self.task {
+            let _ = task.requests.send(request);
+        }
+    }
+
+    fn apply(&mut self, update: Update) {
+        match update {
+            Update::Market { prices, trades, data, live_comments } => {
+                self.live_prices = prices;
+                self.live_trades = trades;
+                match data {
+                    Ok(data) => self.market_data = Some(*data),
+                    Err(e) => self.error = Some(e),
+                }
+                // Failures wait for the next refresh too, rather than retrying every frame.
                 self.last_fetch = Some(Instant::now());
+                if let Some(live) = live_comments {
+                    self.live_comments = live;
+                    self.merge_comments();
+                }
+                self.is_loading = false;
             }
-            Err(e) => {
-                self.error = Some(format!("{}", e));
+            Update::History { range, history, error } => {
+                self.history_loading = false;
+                // A history for a range that has since been cycled past is dropped and refetched.
+                if range == self.history_range {
+                    self.history = history;
+                    self.history_error = error;
+                    self.history_fetched = Some(Instant::now());
+                }
+            }
+            Update::Comments(result) => {
+                match result {
+                    Ok(comments) => {
+                        self.fetched_comments = comments;
+                        self.comments_error = None;
+                    }
+                    Err(e) => self.comments_error = Some(e),
+                }
+                self.comments_loading = false;
+                self.comments_fetched = Some(Instant::now());
+                self.merge_comments();
             }
+            Update::Bought(response) => self.buy_resp = response,
         }
+    }
 
-        self.is_loading = false;
+    fn should_refresh(&self) -> bool {
+        if self.is_loading {
+            return false;
+        }
+        match self.last_fetch {
+            None => true,
+            Some(last) => last.elapsed() >= Duration::from_secs(2),
+        }
     }
 
-    pub fn should_buy_yes(&mut self) -> bool {
-        self.buy_yes
+    /// Comments are refetched now and then, which also brings reaction counts up to date.
+    fn should_refresh_comments(&self) -> bool {
+        if self.comments_loading || self.market_data.as_ref().and_then(|d| d.event_id()).is_none() {
+            return false;
+        }
+        match self.comments_fetched {
+            None => true,
+            Some(last) => last.elapsed() >= Duration::from_secs(30),
+        }
     }
 
-    pub fn should_buy_no(&mut self) -> bool {
-        self.buy_no
+    /// Puts comments from the feed in front of the fetched ones and drops removed ones.
+    /// A comment in both keeps the fetched copy, whose reaction count is complete.
+    fn merge_comments(&mut self) {
+        let live = &self.live_comments;
+        let mut comments: Vec<Comment> = live
+            .comments
+            .iter()
+            .filter(|c| !self.fetched_comments.iter().any(|f| f.id == c.id))
+            .chain(self.fetched_comments.iter())
+            .filter(|c| !live.removed.contains(&c.id))
+            .cloned()
+            .collect();
+        comments.sort_by_key(|c| std::cmp::Reverse(c.created_secs()));
+        self.comments = comments;
     }
 
-    pub fn get_buy_amount(&self) -> Option<f64> {
-        self.input_buffer.parse().ok()
+    fn should_refresh_history(&self) -> bool {
+        if self.history_loading || self.market_data.is_none() {
+            return false;
+        }
+        match self.history_fetched {
+            None => true,
+            Some(last) => last.elapsed() >= Duration::from_secs(60),
+        }
     }
 
-    pub async fn buy(&mut self, yes: bool, amount: f64) {
-        let side = if yes { "Yes" } else { "No" };
+    /// Sends the order to the refresh task; the response shows once it is back.
+    fn buy(&mut self, yes: bool, amount: f64) {
+        let Some(token_ids) = self
+            .market_data
+            .as_ref()
+            .filter(|d| d.is_tradable())
+            .map(|d| d.clob_token_ids.clone())
+        else {
+            self.buy_resp = "This market is closed to new orders".to_string();
+            return;
+        };
         self.buy_resp = "Processing...".to_string();
+        self.request(Request::Buy { token_ids, yes, amount });
+    }
+
+    /// End date countdown, whether orders are accepted and where UMA resolution stands.
+    fn resolution_text(data: &MarketSpecificDetails) -> String {
+        let now = now_secs();
+        let mut lines = Vec::new();
+        let end = parse_timestamp(&data.end_date);
+        match end {
+            Some(end) if end > now => lines.push(format!(
+                "Ends: {} (in {})",
+                format_date_time(end),
+                format_countdown(end - now)
+            )),
+            Some(end) => lines.push(format!(
+                "Ended: {} ({} ago)",
+                format_date_time(end),
+                format_countdown(now - end)
+            )),
+            None => {}
+        }
 
-        let resp = buy_yes(
-            &self.private_key,
-            self.market_data.as_ref().unwrap().clob_token_ids.clone(),
-            side,
-            amount.to_string()
-        ).await;
-
-        match resp {
-            Ok(response) => {
-                let error_msg = response.error_msg.unwrap_or_default();
-                if !error_msg.is_empty() {
-                    self.buy_resp = format!("There was an error buying: {}", error_msg);
-                } else {
-                    self.buy_resp = format!(
-                        "Order Status: {}\nYou spent: ${} and received {} {} shares",
-                        response.status, response.making_amount, response.taking_amount, side
-                    );
+        let trading = if data.is_tradable() {
+            "open"
+        } else if data.closed {
+            "closed"
+        } else {
+            "not accepting orders"
+        };
+        lines.push(format!("Trading: {}", trading));
+
+        let challenge_ends = data.uma_end_date.as_deref().and_then(parse_timestamp).filter(|at| *at > now);
+        let resolution = match data.uma_resolution_status.as_deref().unwrap_or_default() {
+            "" if end.is_some_and(|end| end <= now) && !data.closed => Some("awaiting a proposal".to_string()),
+            "" => None,
+            "proposed" => {
+                let mut text = String::from("proposed");
+                if let Some((outcome, price)) = data.leading_outcome(0.95) {
+                    text.push_str(&format!(", likely {} (priced {:.2})", outcome, price));
+                }
+                if let Some(at) = challenge_ends {
+                    text.push_str(&format!(", challenge period ends in {}", format_countdown(at - now)));
                 }
+                Some(text)
             }
-            Err(e) => {
-                self.buy_resp = format!("Buy error: {}", e);
+            "disputed" => Some("disputed, waiting for the UMA vote".to_string()),
+            "resolved" => Some(match data.leading_outcome(0.99) {
+                Some((outcome, _)) => format!("resolved to {}", outcome),
+                None => "resolved".to_string(),
+            }),
+            other => Some(other.to_string()),
+        };
+        if let Some(resolution) = resolution {
+            lines.push(format!("Resolution: {}", resolution));
+        }
+        if let Some(source) = data.resolution_source.as_deref().filter(|s| !s.is_empty()) {
+            lines.push(format!("Resolution source: {}", source));
+        }
+        lines.join("\n")
+    }
+
+    /// Opens the buy dialog, unless the market cannot take orders.
+    fn start_buy(&mut self, mode: InputMode) {
+        match self.market_data {
+            Some(ref data) if data.is_tradable() => {
+                self.input_mode = mode;
+                self.input_buffer.clear();
+            }
+            Some(_) => self.buy_resp = "This market is closed to new orders".to_string(),
+            None => self.buy_resp = "Market data is still loading".to_string(),
+        }
+    }
+
+    fn live_price_text(&self) -> String {
+        let Some(ref prices) = self.live_prices else {
+            return "Live Prices (session)\n\nNo trades seen yet".to_string();
+        };
+        let lines: Vec<String> = prices
+            .outcomes
+            .iter()
+            .map(|o| {
+                format!(
+                    "{}: last {:.3} ({})  open {:.3}  high {:.3}  low {:.3}  vwap {:.3}  trades {}",
+                    o.outcome,
+                    o.last,
+                    format_change(o.change()),
+                    o.open,
+                    o.high,
+                    o.low,
+                    o.vwap(),
+                    o.trades
+                )
+            })
+            .collect();
+        format!("Live Prices (session)\n\n{}", lines.join("\n"))
+    }
+
+    fn render_chart(&self, frame: &mut Frame, area: Rect) {
+        let theme = theme::current();
+        let block = Block::default()
+            .title(format!("Price History ({}) [r range, 1-9 toggle outcome]", self.history_range.label()))
+            .borders(Borders::ALL)
+            .border_style(theme.border(false));
+
+        let has_points = self.history.iter().any(|(_, points)| !points.is_empty());
+        if !has_points {
+            let text = match (&self.history_error, self.history_fetched) {
+                (Some(error), _) => format!("Error: {}", error),
+                (None, None) => "Loading...".to_string(),
+                (None, Some(_)) => "No price history".to_string(),
+            };
+            frame.render_widget(Paragraph::new(text).block(block), area);
+            return;
+        }
+
+        let x_min = self
+            .history
+            .iter()
+            .filter_map(|(_, points)| points.first().map(|p| p.0))
+            .fold(f64::MAX, f64::min);
+        let x_max = (now_secs() as f64).max(x_min + 1.0);
+
+        let live_points: Vec<Vec<(f64, f64)>> = self
+            .history
+            .iter()
+            .map(|(name, _)| {
+                self.live_trades
+                    .iter()
+                    .filter(|t| &t.outcome == name && t.timestamp as f64 >= x_min)
+                    .map(|t| (t.timestamp as f64, t.price))
+                    .collect()
+            })
+            .collect();
+
+        let mut datasets = Vec::new();
+        for (idx, (name, points)) in self.history.iter().enumerate() {
+            if self.hidden_outcomes.contains(&idx) {
+                continue;
+            }
+            let color = theme.series[idx % theme.series.len()];
+            let last = points.last().map(|p| format!(" {:.2}", p.1)).unwrap_or_default();
+            datasets.push(
+                Dataset::default()
+                    .name(format!("{}. {}{}", idx + 1, name, last))
+                    .marker(Marker::Braille)
+                    .graph_type(GraphType::Line)
+                    .style(Style::default().fg(color))
+                    .data(points),
+            );
+            datasets.push(
+                Dataset::default()
+                    .marker(Marker::Dot)
+                    .graph_type(GraphType::Scatter)
+                    .style(Style::default().fg(color).add_modifier(Modifier::BOLD))
+                    .data(&live_points[idx]),
+            );
+        }
+
+        let format_x = |t: f64| match self.history_range {
+            HistoryRange::OneHour | HistoryRange::SixHours => format_time(t as u64),
+            _ => format_date_time(t as u64),
+        };
+
+        let chart = Chart::new(datasets)
+            .block(block)
+            .x_axis(
+                Axis::default()
+                    .style(theme.muted())
+                    .bounds([x_min, x_max])
+                    .labels(vec![format_x(x_min), format_x(x_max)]),
+            )
+            .y_axis(
+                Axis::default()
+                    .style(theme.muted())
+                    .bounds([0.0, 1.0])
+                    .labels(vec!["0.00", "0.50", "1.00"]),
+            );
+
+        frame.render_widget(chart, area);
+    }
+
+    /// Shares the author holds in each of this market's outcomes, e.g. `Yes 1.20K`. An
+    /// author holding only other markets of the event is marked as a holder.
+    fn position_badges(&self, comment: &Comment) -> Vec<Span<'static>> {
+        let theme = theme::current();
+        let positions = comment.profile.as_ref().map(|p| p.positions.as_slice()).unwrap_or_default();
+        let Some(ref data) = self.market_data else {
+            return vec![];
+        };
+        let outcomes = data.outcome_names();
+        let tokens = data.token_ids();
+
+        let mut badges = Vec::new();
+        for position in positions {
+            let Some(idx) = tokens.iter().position(|t| *t == position.token_id) else {
+                continue;
+            };
+            // Sizes are in millionths of a share.
+            let shares = position.position_size.parse::<f64>().unwrap_or(0.0) / 1e6;
+            if shares < 1.0 {
+                continue;
+            }
+            let name = outcomes.get(idx).cloned().unwrap_or_else(|| format!("Outcome {}", idx + 1));
+            let color = theme.series[idx % theme.series.len()];
+            badges.push(Span::raw(" "));
+            badges.push(Span::styled(
+                format!("[{} {}]", name, format_volume(shares)),
+                Style::default().fg(color),
+            ));
+        }
+        if badges.is_empty() && !positions.is_empty() {
+            badges.push(Span::styled(" [holder]", theme.muted()));
+        }
+        badges
+    }
+
+    /// Top of the book for the first outcome, asks above bids so the spread sits in the middle.
+    fn render_order_book(&self, frame: &mut Frame, area: Rect) {
+        const DEPTH: usize = 5;
+        let theme = theme::current();
+        let (Some(feed), Some(data)) = (&self.order_book, &self.market_data) else {
+            return;
+        };
+        let outcome = data.outcome_names().into_iter().next().unwrap_or_default();
+        let block = Block::default()
+            .title(format!("Order Book ({})", outcome))
+            .borders(Borders::ALL)
+            .border_style(theme.border(false));
+
+        let book = feed.token_ids.first().and_then(|token| feed.book(token));
+        let lines: Vec<Line> = match book {
+            Some(book) => {
+                let level = |(price, size): (f64, f64), up: bool| {
+                    Line::from(vec![
+                        Span::styled(format!("{:>6.1}¢", price * 100.0), Style::default().fg(theme.direction(up))),
+                        Span::raw(format!("  {:>12}", format!("{:.2}", size))),
+                    ])
+                };
+                let mut lines: Vec<Line> = book.asks(DEPTH).into_iter().rev().map(|l| level(l, false)).collect();
+                lines.push(Line::from(Span::styled("  ------", theme.muted())));
+                lines.extend(book.bids(DEPTH).into_iter().map(|l| level(l, true)));
+                lines
+            }
+            None => vec![Line::from(Span::styled("Connecting...", theme.muted()))],
+        };
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    fn render_comments(&mut self, frame: &mut Frame, area: Rect) {
+        let theme = theme::current();
+        self.comments_area = area;
+        let block = Block::default()
+            .title(format!("Comments ({}) [c switch pane]", self.comments.len()))
+            .borders(Borders::ALL)
+            .border_style(theme.border(self.comments_focused));
+
+        if self.comments.is_empty() {
+            let text = match (&self.comments_error, self.comments_fetched) {
+                (Some(error), _) => format!("Error: {}", error),
+                (None, None) => "Loading...".to_string(),
+                (None, Some(_)) => "No comments".to_string(),
+            };
+            frame.render_widget(Paragraph::new(text).block(block), area);
+            return;
+        }
+
+        let mut lines = Vec::new();
+        for comment in &self.comments {
+            let author = comment
+                .author_name()
+                .map(String::from)
+                .unwrap_or_else(|| format_address(&comment.user_address));
+            let mut header = vec![];
+            if comment.parent_comment_id.is_some() {
+                header.push(Span::styled("↳ ", theme.muted()));
             }
+            header.push(Span::styled(author, Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)));
+            header.extend(self.position_badges(comment));
+            header.push(Span::styled(format!("  {}", format_date_time(comment.created_secs())), theme.muted()));
+            if comment.reaction_count > 0 {
+                header.push(Span::styled(format!("  ♥ {}", comment.reaction_count), theme.muted()));
+            }
+            lines.push(Line::from(header));
+            lines.extend(comment.body.lines().map(|line| Line::raw(line.to_string())));
+            lines.push(Line::raw(""));
         }
 
-        self.buy_yes = false;
-        self.buy_no = false;
+        let paragraph = Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false })
+            .scroll((self.comments_scroll, 0));
+        frame.render_widget(paragraph, area);
     }
 
-    fn render_input_popup(&self, frame: &mut Frame, area: Rect) {
+    /// Submits the buy amount; anything that is not a number is discarded.
+    fn confirm_input(&mut self) {
+        if !self.input_buffer.is_empty() {
+            if self.input_buffer.parse::<f64>().is_ok() {
+                match self.input_mode {
+                    InputMode::BuyYes => self.buy_yes = true,
+                    InputMode::BuyNo => self.buy_no = true,
+                    _ => {}
+                }
+            } else {
+                self.input_buffer.clear();
+            }
+        }
+        self.input_mode = InputMode::Normal;
+    }
+
+    fn cancel_input(&mut self) {
+        self.input_mode = InputMode::Normal;
+        self.input_buffer.clear();
+    }
+
+    fn render_input_popup(&mut self, frame: &mut Frame, area: Rect) {
         let popup_width = 40;
         let popup_height = 5;
 
@@ -143,6 +619,7 @@ impl DetailPage {
 
         frame.render_widget(Clear, popup_area);
 
+        let theme = theme::current();
         let side = match self.input_mode {
             InputMode::BuyYes => "YES",
             InputMode::BuyNo => "NO",
@@ -152,26 +629,45 @@ impl DetailPage {
         let block = Block::default()
             .title(format!(" Buy {} - Enter Amount ", side))
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Green));
+            .border_style(Style::default().fg(theme.up));
 
         let input_text = Line::from(vec![
             Span::raw("$ "),
-            Span::styled(&self.input_buffer, Style::default().fg(Color::White)),
-            Span::styled("│", Style::default().fg(Color::Gray)), // cursor
+            Span::styled(&self.input_buffer, Style::default().fg(theme.text)),
+            Span::styled("│", theme.muted()), // cursor
         ]);
 
-        let help_line = Line::from(vec![
-            Span::styled("Enter", Style::default().fg(Color::Yellow)),
-            Span::raw(" Confirm  "),
-            Span::styled("Esc", Style::default().fg(Color::Yellow)),
-            Span::raw(" Cancel"),
+        let confirm = Line::from(vec![
+            Span::raw("[ "),
+            Span::styled("Enter", theme.key()),
+            Span::raw(" Confirm ]"),
+        ]);
+        let cancel = Line::from(vec![
+            Span::raw("[ "),
+            Span::styled("Esc", theme.key()),
+            Span::raw(" Cancel ]"),
         ]);
+        // Buttons sit on the third line inside the border, two columns apart.
+        self.confirm_button = Rect {
+            x: popup_area.x + 1,
+            y: popup_area.y + 3,
+            width: confirm.width() as u16,
+            height: 1,
+        };
+        self.cancel_button = Rect {
+            x: self.confirm_button.right() + 2,
+            width: cancel.width() as u16,
+            ..self.confirm_button
+        };
 
-        let content = vec![input_text, Line::raw(""), help_line];
+        let mut button_line = confirm;
+        button_line.push_span(Span::raw("  "));
+        button_line.extend(cancel);
+        let content = vec![input_text, Line::raw(""), button_line];
 
         let paragraph = Paragraph::new(content)
             .block(block)
-            .style(Style::default().bg(Color::Black));
+            .style(Style::default().bg(theme.popup_bg));
 
         frame.render_widget(paragraph, popup_area);
     }
@@ -179,6 +675,7 @@ impl DetailPage {
 
 impl Page for DetailPage {
     fn render(&mut self, frame: &mut Frame, area: Rect, _state: &SharedState) {
+        let theme = theme::current();
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .margin(2)
@@ -192,14 +689,14 @@ impl Page for DetailPage {
         let title_block = Block::default()
             .title(self.title.clone())
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Cyan));
+            .border_style(theme.title());
 
         frame.render_widget(title_block, chunks[0]);
 
         let content_block = Block::default()
             .title("Details")
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::White));
+            .border_style(theme.border(false));
 
         let display_content = if self.is_loading && self.market_data.is_none() {
             "Loading...".to_string()
@@ -207,10 +704,11 @@ impl Page for DetailPage {
             format!("Error: {}", error)
         } else if let Some(ref data) = self.market_data {
             format!(
-                "{}\n\nMarket Data\n\nDescription: {}\nActive: {}\nLiquidity: {}\nVolume: {}\n24hr|1wk|1mo|1yr vol : {}|{}|{}|{}\nBid/Ask: {}/{}\n\n\n{}",
+                "{}\n\nMarket Data\n\nDescription: {}\nActive: {}\n{}\nLiquidity: {}\nVolume: {}\n24hr|1wk|1mo|1yr vol : {}|{}|{}|{}\nBid/Ask: {}/{}\n\n{}\n\n\n{}",
                 self.content,
                 data.description,
                 data.active,
+                Self::resolution_text(data),
                 data.liquidity,
                 data.volume,
                 data.volume24hr.unwrap_or(0.0),
@@ -219,6 +717,7 @@ impl Page for DetailPage {
                 data.volume1yr.unwrap_or(0.0),
                 data.best_ask,
                 data.best_bid,
+                self.live_price_text(),
                 self.buy_resp
             )
         } else {
@@ -231,23 +730,57 @@ impl Page for DetailPage {
             .scroll((self.scroll_offset, 0));
         let status = if self.is_loading { " (refreshing...)" } else { "" };
 
-        frame.render_widget(content_paragraph, chunks[1]);
-
-        let help_text = Line::from(vec![
-            Span::styled("Esc/Backspace", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-            Span::raw(" Go Back  "),
-            Span::styled("↑/↓", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-            Span::raw(" Scroll  "),
-            Span::styled("y/n", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
-            Span::raw(" Buy Yes/No  "),
-            Span::styled("q", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-            Span::raw(" Quit"),
-            Span::styled(status, Style::default().fg(Color::DarkGray)),
+        let content_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(chunks[1]);
+
+        let right_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+            .split(content_chunks[1]);
+
+        // The book takes the bottom of the left column while the market trades.
+        let left_chunks = if self.order_book.is_some() {
+            Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(0), Constraint::Length(13)])
+                .split(content_chunks[0])
+        } else {
+            Layout::default()
+                .constraints([Constraint::Min(0)])
+                .split(content_chunks[0])
+        };
+
+        frame.render_widget(content_paragraph, left_chunks[0]);
+        if let Some(&book_area) = left_chunks.get(1) {
+            self.render_order_book(frame, book_area);
+        }
+        self.render_chart(frame, right_chunks[0]);
+        self.render_comments(frame, right_chunks[1]);
+
+        let mut help_text = keymap::get().detail.help_line(&[
+            (Action::Back, "Go Back"),
+            (Action::Up, "Scroll Up"),
+            (Action::Down, "Scroll Down"),
+            (Action::BuyYes, "Buy Yes"),
+            (Action::BuyNo, "Buy No"),
+            (Action::CycleRange, "Chart Range"),
+            (Action::NextPanel, "Comments"),
         ]);
+        help_text.push_span(Span::styled("1-9", theme.key()));
+        help_text.push_span(Span::raw(" Toggle Outcome  "));
+        help_text.extend(keymap::get().detail.help_line(&[
+            (Action::NextTab, "Next Tab"),
+            (Action::CloseTab, "Close Tab"),
+            (Action::ToggleSplit, "Split"),
+            (Action::Quit, "Quit"),
+        ]));
+        help_text.push_span(Span::styled(status, theme.muted()));
 
         let help_block = Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::DarkGray));
+            .border_style(theme.muted());
 
         frame.render_widget(
             Paragraph::new(help_text).block(help_block),
@@ -259,27 +792,15 @@ impl Page for DetailPage {
         }
     }
 
-    fn handle_input(&mut self, key: KeyEvent, _state: &SharedState) -> PageAction {
+    fn handle_input(&mut self, key: KeyEvent, state: &SharedState) -> PageAction {
         if self.input_mode != InputMode::Normal {
             match key.code {
                 KeyCode::Esc => {
-                    self.input_mode = InputMode::Normal;
-                    self.input_buffer.clear();
+                    self.cancel_input();
                     PageAction::None
                 }
                 KeyCode::Enter => {
-                    if !self.input_buffer.is_empty() {
-                        if self.input_buffer.parse::<f64>().is_ok() {
-                            match self.input_mode.clone() {
-                                InputMode::BuyYes => self.buy_yes = true,
-                                InputMode::BuyNo => self.buy_no = true,
-                                _ => {}
-                            }
-                        } else {
-                            self.input_buffer.clear();
-                        }
-                    }
-                    self.input_mode = InputMode::Normal;
+                    self.confirm_input();
                     PageAction::None
                 }
                 KeyCode::Backspace => {
@@ -295,29 +816,195 @@ impl Page for DetailPage {
                 _ => PageAction::None,
             }
         } else {
-            match key.code {
-                KeyCode::Char('q') => PageAction::Quit,
-                KeyCode::Esc | KeyCode::Backspace => PageAction::GoBack,
-                KeyCode::Up => {
-                    self.scroll_offset = self.scroll_offset.saturating_sub(1);
-                    PageAction::None
-                }
-                KeyCode::Down => {
-                    self.scroll_offset = self.scroll_offset.saturating_add(1);
-                    PageAction::None
-                }
-                KeyCode::Char('y') => {
-                    self.input_mode = InputMode::BuyYes;
-                    self.input_buffer.clear();
-                    PageAction::None
-                }
-                KeyCode::Char('n') => {
-                    self.input_mode = InputMode::BuyNo;
-                    self.input_buffer.clear();
-                    PageAction::None
-                }
-                _ => PageAction::None,
+            match keymap::get().detail.action(&key) {
+                Some(action) => self.handle_action(action, state),
+                // Outcome toggles follow the legend numbering, so they are not remappable.
+                None => match key.code {
+                    KeyCode::Char(c @ '1'..='9') => {
+                        let idx = c as usize - '1' as usize;
+                        if !self.hidden_outcomes.remove(&idx) {
+                            self.hidden_outcomes.insert(idx);
+                        }
+                        PageAction::None
+                    }
+                    _ => PageAction::None,
+                },
+            }
+        }
+    }
+
+    fn handle_mouse(&mut self, mouse: Mouse, state: &SharedState) -> PageAction {
+        if self.input_mode != InputMode::Normal {
+            if mouse.is_click() && mouse.is_in(self.confirm_button) {
+                self.confirm_input();
+            } else if mouse.is_click() && mouse.is_in(self.cancel_button) {
+                self.cancel_input();
             }
+            return PageAction::None;
+        }
+        // The wheel scrolls whichever pane it is over.
+        if matches!(mouse.kind, MouseKind::ScrollUp | MouseKind::ScrollDown) {
+            self.comments_focused = mouse.is_in(self.comments_area);
+        }
+        match mouse.kind {
+            MouseKind::ScrollUp => self.handle_action(Action::Up, state),
+            MouseKind::ScrollDown => self.handle_action(Action::Down, state),
+            MouseKind::Click | MouseKind::DoubleClick => PageAction::None,
+        }
+    }
+
+    fn handle_action(&mut self, action: Action, _state: &SharedState) -> PageAction {
+        match action {
+            Action::Quit => PageAction::Quit,
+            Action::Back => PageAction::GoBack,
+            Action::Up => {
+                let offset = if self.comments_focused { &mut self.comments_scroll } else { &mut self.scroll_offset };
+                *offset = offset.saturating_sub(1);
+                PageAction::None
+            }
+            Action::Down => {
+                let offset = if self.comments_focused { &mut self.comments_scroll } else { &mut self.scroll_offset };
+                *offset = offset.saturating_add(1);
+                PageAction::None
+            }
+            Action::NextPanel => {
+                self.comments_focused = !self.comments_focused;
+                PageAction::None
+            }
+            Action::BuyYes => {
+                self.start_buy(InputMode::BuyYes);
+                PageAction::None
+            }
+            Action::BuyNo => {
+                self.start_buy(InputMode::BuyNo);
+                PageAction::None
+            }
+            Action::CycleRange => {
+                self.history_range = self.history_range.next();
+                self.history_fetched = None;
+                PageAction::None
+            }
+            _ => PageAction::None,
+        }
+    }
+
+    fn is_capturing_input(&self) -> bool {
+        self.input_mode != InputMode::Normal
+    }
+
+    fn help_extras(&self) -> &'static [(&'static str, &'static str)] {
+        &[("1-9", "Show or hide an outcome on the chart")]
+    }
+}
+
+/// Network work a tab hands to its refresh task.
+enum Request {
+    /// Live prices, trades and comments from the state actor, plus the market itself.
+    Market { event_id: Option<u64> },
+    History { outcomes: Vec<String>, tokens: Vec<String>, range: HistoryRange },
+    Comments { event_id: u64 },
+    Buy { token_ids: String, yes: bool, amount: f64 },
+}
+
+/// A finished `Request`, applied to the tab on its next `refresh`.
+enum Update {
+    Market {
+        prices: Option<MarketPrices>,
+        trades: Vec<Trade>,
+        data: Result<Box<MarketSpecificDetails>, String>,
+        live_comments: Option<LiveComments>,
+    },
+    History { range: HistoryRange, history: Vec<(String, Vec<(f64, f64)>)>, error: Option<String> },
+    Comments(Result<Vec<Comment>, String>),
+    /// What to tell the user about an order.
+    Bought(String),
+}
+
+/// A tab's background task. It stops once the tab is closed and its end of the
+/// request channel is dropped.
+struct RefreshTask {
+    requests: mpsc::UnboundedSender<Request>,
+    updates: mpsc::UnboundedReceiver<Update>,
+}
+
+impl RefreshTask {
+    fn spawn(slug: String, private_key: String, events: EventSender) -> Self {
+        let (requests, request_rx) = mpsc::unbounded_channel();
+        let (update_tx, updates) = mpsc::unbounded_channel();
+        tokio::spawn(run_refresh(slug, private_key, events, request_rx, update_tx));
+        Self { requests, updates }
+    }
+}
+
+/// Serves one tab's requests in order.
+async fn run_refresh(
+    slug: String,
+    private_key: String,
+    events: EventSender,
+    mut requests: mpsc::UnboundedReceiver<Request>,
+    updates: mpsc::UnboundedSender<Update>,
+) {
+    while let Some(request) = requests.recv().await {
+        let update = match request {
+            Request::Market { event_id } => {
+                let prices = market_prices(&events, &slug).await;
+                let trades = market_trades(&events, &slug).await;
+                let data = get_market_from_slug(&slug).await.map(Box::new).map_err(|e| format!("{}", e));
+                let event_id = data.as_ref().ok().and_then(|d| d.event_id()).or(event_id);
+                let live_comments = match event_id {
+                    Some(event_id) => Some(event_comments(&events, event_id).await),
+                    None => None,
+                };
+                Update::Market { prices, trades, data, live_comments }
+            }
+            Request::History { outcomes, tokens, range } => fetch_price_history(outcomes, tokens, range).await,
+            Request::Comments { event_id } => {
+                Update::Comments(get_comments(event_id).await.map_err(|e| format!("{}", e)))
+            }
+            Request::Buy { token_ids, yes, amount } => {
+                Update::Bought(place_buy(&slug, &private_key, token_ids, yes, amount).await)
+            }
+        };
+        if updates.send(update).is_err() {
+            break;
+        }
+    }
+}
+
+async fn fetch_price_history(outcomes: Vec<String>, tokens: Vec<String>, range: HistoryRange) -> Update {
+    let mut history = Vec::with_capacity(tokens.len());
+    let mut error = None;
+    for (idx, token) in tokens.iter().enumerate() {
+        let name = outcomes.get(idx).cloned().unwrap_or_else(|| format!("Outcome {}", idx + 1));
+        match get_price_history(token, range).await {
+            Ok(points) => history.push((name, points)),
+            Err(e) => {
+                error = Some(format!("{}", e));
+                history.push((name, vec![]));
+            }
+        }
+    }
+    Update::History { range, history, error }
+}
+
+async fn place_buy(slug: &str, private_key: &str, token_ids: String, yes: bool, amount: f64) -> String {
+    let side = if yes { "Yes" } else { "No" };
+    match buy_yes(private_key, token_ids, side, amount.to_string()).await {
+        Ok(response) => {
+            let error_msg = response.error_msg.unwrap_or_default();
+            if !error_msg.is_empty() {
+                tracing::warn!(slug, "Order rejected: {}", error_msg);
+                format!("There was an error buying: {}", error_msg)
+            } else {
+                format!(
+                    "Order Status: {}\nYou spent: ${} and received {} {} shares",
+                    response.status, response.making_amount, response.taking_amount, side
+                )
+            }
+        }
+        Err(e) => {
+            tracing::error!(slug, "Buy failed: {}", e);
+            format!("Buy error: {}", e)
         }
     }
 }
\ No newline at end of file
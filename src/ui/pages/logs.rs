@@ -0,0 +1,167 @@
+use crossterm::event::KeyEvent;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::Span,
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, Wrap},
+};
+use tracing::Level;
+
+use crate::config;
+use crate::data::state::SharedState;
+use crate::logging::{self, LogEntry};
+use crate::ui::components::format::format_time;
+use crate::ui::components::mouse::{Mouse, MouseKind};
+use crate::ui::components::scroll::ScrollState;
+use crate::ui::keymap::{self, Action};
+use crate::ui::theme;
+use super::{Page, PageAction};
+
+/// Recent warnings and errors, newest first, with the selected one shown in full.
+pub struct LogsPage {
+    scroll: ScrollState,
+    entries: Vec<LogEntry>,
+    /// Where the list was drawn last frame, for mouse hit-testing.
+    list_area: Rect,
+}
+
+impl LogsPage {
+    pub fn new() -> Self {
+        Self {
+            scroll: ScrollState::default(),
+            entries: vec![],
+            list_area: Rect::default(),
+        }
+    }
+}
+
+impl Page for LogsPage {
+    fn render(&mut self, frame: &mut Frame, area: Rect, _state: &SharedState) {
+        self.entries = logging::recent();
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(2)
+            .constraints([
+                Constraint::Min(0),
+                Constraint::Length(6),
+                Constraint::Length(3),
+            ])
+            .split(area);
+
+        let theme = theme::current();
+        self.list_area = chunks[0];
+        let list_block = Block::default()
+            .title("Warnings and Errors")
+            .title_bottom(self.scroll.position(self.entries.len()))
+            .borders(Borders::ALL)
+            .border_style(theme.border(true));
+
+        if self.entries.is_empty() {
+            frame.render_widget(Paragraph::new("No warnings or errors").block(list_block), chunks[0]);
+        } else {
+            // Borders and the header line.
+            let height = chunks[0].height.saturating_sub(3) as usize;
+            let range = self.scroll.visible(self.entries.len(), height);
+            let selected = self.scroll.relative();
+            let rows: Vec<Row> = self.entries[range]
+                .iter()
+                .enumerate()
+                .map(|(idx, entry)| {
+                    let level_color = if entry.level == Level::ERROR { theme.error } else { theme.accent };
+                    let row = Row::new(vec![
+                        Cell::from(format_time(entry.at)),
+                        Cell::from(Span::styled(entry.level.to_string(), Style::default().fg(level_color))),
+                        Cell::from(short_target(&entry.target).to_string()),
+                        Cell::from(entry.message.clone()),
+                    ]);
+                    if idx == selected {
+                        row.style(theme.highlight())
+                    } else {
+                        row
+                    }
+                })
+                .collect();
+
+            let widths = [
+                Constraint::Length(8),
+                Constraint::Length(5),
+                Constraint::Length(20),
+                Constraint::Fill(1),
+            ];
+            let header = Row::new(vec!["Time", "Level", "Module", "Message"]).style(theme.muted());
+            frame.render_widget(Table::new(rows, widths).header(header).block(list_block), chunks[0]);
+        }
+
+        let detail = self
+            .entries
+            .get(self.scroll.selected)
+            .map(|entry| format!("{}\n{}", entry.target, entry.message))
+            .unwrap_or_default();
+        let detail_block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(theme.border(false));
+        frame.render_widget(
+            Paragraph::new(detail).wrap(Wrap { trim: false }).block(detail_block),
+            chunks[1],
+        );
+
+        let mut help_text = keymap::get().logs.help_line(&[
+            (Action::Back, "Go Back"),
+            (Action::Up, "Up"),
+            (Action::Down, "Down"),
+            (Action::Quit, "Quit"),
+        ]);
+        let log_dir = &config::get().log_dir;
+        if !log_dir.is_empty() {
+            help_text.push_span(Span::styled(format!("Full log in {}/", log_dir), theme.muted()));
+        }
+        let help_block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(theme.muted());
+        frame.render_widget(Paragraph::new(help_text).block(help_block), chunks[2]);
+    }
+
+    fn handle_input(&mut self, key: KeyEvent, state: &SharedState) -> PageAction {
+        match keymap::get().logs.action(&key) {
+            Some(action) => self.handle_action(action, state),
+            None => PageAction::None,
+        }
+    }
+
+    fn handle_mouse(&mut self, mouse: Mouse, _state: &SharedState) -> PageAction {
+        let len = self.entries.len();
+        match mouse.kind {
+            MouseKind::ScrollUp => self.scroll.up(),
+            MouseKind::ScrollDown => self.scroll.down(len),
+            MouseKind::Click | MouseKind::DoubleClick => {
+                if let Some(row) = mouse.row_in(self.list_area, 1) {
+                    self.scroll.select_visible(row, len);
+                }
+            }
+        }
+        PageAction::None
+    }
+
+    fn handle_action(&mut self, action: Action, _state: &SharedState) -> PageAction {
+        let len = self.entries.len();
+        match action {
+            Action::Quit => return PageAction::Quit,
+            Action::Back => return PageAction::GoBack,
+            Action::Up => self.scroll.up(),
+            Action::Down => self.scroll.down(len),
+            Action::PageUp => self.scroll.page_up(),
+            Action::PageDown => self.scroll.page_down(len),
+            Action::Home => self.scroll.home(),
+            Action::End => self.scroll.end(len),
+            _ => {}
+        }
+        PageAction::None
+    }
+}
+
+/// Drops the crate name from our own modules, `pmterm::data::ws` becoming `data::ws`.
+fn short_target(target: &str) -> &str {
+    target.strip_prefix("pmterm::").unwrap_or(target)
+}
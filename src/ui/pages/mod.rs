@@ -1,35 +1,68 @@
 mod dashboard;
 mod detail;
+mod logs;
+mod trader;
 mod wallet;
+mod workspace;
 
 
 
 pub use dashboard::DashboardPage;
 pub use detail::DetailPage;
+pub use logs::LogsPage;
+pub use trader::TraderPage;
 pub use wallet::WalletPage;
+pub use workspace::WorkspacePage;
 
 use crossterm::event::KeyEvent;
 use ratatui::{Frame, layout::Rect};
-use crate::data::state::SharedState;
-use super::app::App;
+use crate::data::state::{SharedState, TimeWindow};
+use super::components::mouse::Mouse;
+use super::keymap::Action;
 
 #[derive(Clone, Copy, PartialEq)]
 pub enum PageType {
     Dashboard,
     Detail,
     Wallet,
+    Trader,
+    Logs,
 }
 
 pub enum PageAction {
     None,
     NavigateToDetail { title: String, content: String, identifier: String },
     NavigateToWallet { title: String },
+    NavigateToTrader { address: String },
+    NavigateToLogs,
+    CancelAllOrders,
+    /// Write the lists of this statistics window, the tape and the wallet's positions
+    /// and orders to files.
+    Export { window: TimeWindow },
     GoBack,
+    /// Reopen the page last left with `GoBack`.
+    GoForward,
     Quit,
 }
 
 pub trait Page {
     fn render(&mut self, frame: &mut Frame, area: Rect, state: &SharedState);
     fn handle_input(&mut self, key: KeyEvent, state: &SharedState) -> PageAction;
+    /// Runs a named action, whether it came from a key or the command palette.
+    fn handle_action(&mut self, action: Action, state: &SharedState) -> PageAction;
 
+    /// Clicks and wheel scrolls, hit-tested against the areas of the last render.
+    fn handle_mouse(&mut self, _mouse: Mouse, _state: &SharedState) -> PageAction {
+        PageAction::None
+    }
+
+    /// True while the page is reading free text, so global keys like `?` are left to it.
+    fn is_capturing_input(&self) -> bool {
+        false
+    }
+
+    /// Keys the page handles outside the keymap, as (keys, description) for the help overlay.
+    fn help_extras(&self) -> &'static [(&'static str, &'static str)] {
+        &[]
+    }
 }
\ No newline at end of file
@@ -0,0 +1,287 @@
+use std::time::{Duration, Instant};
+use crossterm::event::KeyEvent;
+use tokio::sync::mpsc;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::Span,
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+};
+use crate::data::get_trader::get_trader_trades;
+use crate::data::pipeline::{trader_volume, EventSender};
+use crate::data::state::{SharedState, Trade};
+use crate::ui::components::mouse::{Mouse, MouseKind};
+use crate::ui::components::format::{format_address, format_time, format_volume};
+use crate::ui::keymap::{self, Action};
+use crate::ui::theme;
+use super::{Page, PageAction};
+
+pub struct TraderPage {
+    pub address: String,
+    pub trades: Vec<Trade>,
+    pub selected: usize,
+    pub session_volume: f64,
+    pub last_fetch: Option<Instant>,
+    pub is_loading: bool,
+    pub error: Option<String>,
+    /// Where the trades table was drawn last frame, for mouse hit-testing.
+    trades_area: Rect,
+    /// Started on the first `refresh`.
+    task: Option<FetchTask>,
+}
+
+impl TraderPage {
+    pub fn new(address: String) -> Self {
+        Self {
+            address,
+            trades: vec![],
+            selected: 0,
+            session_volume: 0.0,
+            last_fetch: None,
+            is_loading: false,
+            error: None,
+            trades_area: Rect::default(),
+            task: None,
+        }
+    }
+
+    /// Applies a finished fetch and starts the next one when it is due. Never waits on
+    /// the network, so the UI loop can call it every frame.
+    pub fn refresh(&mut self, events: &EventSender) {
+        let task = self
+            .task
+            .get_or_insert_with(|| FetchTask::spawn(self.address.clone(), events.clone()));
+        let fetched: Vec<Fetched> = std::iter::from_fn(|| task.updates.try_recv().ok()).collect();
+        for fetched in fetched {
+            self.apply(fetched);
+        }
+
+        if self.should_refresh()
+            && let Some(task) = &self.task
+        {
+            self.is_loading = true;
+            self.error = None;
+            let _ = task.requests.send(());
+        }
+    }
+
+    fn should_refresh(&self) -> bool {
+        if self.is_loading {
+            return false;
+        }
+        match self.last_fetch {
+            None => true,
+            Some(last) => last.elapsed() >= Duration::from_secs(10),
+        }
+    }
+
+    fn apply(&mut self, fetched: Fetched) {
+        if let Some(volume) = fetched.session_volume {
+            self.session_volume = volume;
+        }
+
+        match fetched.trades {
+            Ok(trades) => {
+                self.trades = trades;
+                self.selected = self.selected.min(self.trades.len().saturating_sub(1));
+            }
+            Err(e) => {
+                self.error = Some(e);
+            }
+        }
+
+        self.last_fetch = Some(Instant::now());
+        self.is_loading = false;
+    }
+
+    fn display_name(&self) -> String {
+        self.trades
+            .iter()
+            .find_map(|t| t.trader_name.clone())
+            .unwrap_or_else(|| format_address(&self.address))
+    }
+}
+
+impl Page for TraderPage {
+    fn render(&mut self, frame: &mut Frame, area: Rect, _state: &SharedState) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(2)
+            .constraints([
+                Constraint::Length(4),
+                Constraint::Min(0),
+                Constraint::Length(3),
+            ])
+            .split(area);
+
+        let theme = theme::current();
+        let header_block = Block::default()
+            .title(format!("Trader: {}", self.display_name()))
+            .borders(Borders::ALL)
+            .border_style(theme.title());
+
+        let header_text = format!(
+            "Address: {}\nSession volume: {}",
+            self.address,
+            format_volume(self.session_volume)
+        );
+        frame.render_widget(Paragraph::new(header_text).block(header_block), chunks[0]);
+
+        self.trades_area = chunks[1];
+        let trades_block = Block::default()
+            .title("Recent Trades")
+            .borders(Borders::ALL)
+            .border_style(theme.border(false));
+
+        if let Some(ref error) = self.error {
+            frame.render_widget(
+                Paragraph::new(format!("Error: {}", error)).block(trades_block),
+                chunks[1],
+            );
+        } else if self.trades.is_empty() {
+            let text = if self.is_loading || self.last_fetch.is_none() { "Loading..." } else { "No trades found" };
+            frame.render_widget(Paragraph::new(text).block(trades_block), chunks[1]);
+        } else {
+            let rows: Vec<Row> = self
+                .trades
+                .iter()
+                .enumerate()
+                .map(|(idx, t)| {
+                    let side_color = theme.direction(t.is_buy());
+                    let row = Row::new(vec![
+                        Cell::from(format_time(t.timestamp)),
+                        Cell::from(Span::styled(t.side.clone(), Style::default().fg(side_color))),
+                        Cell::from(t.outcome.clone()),
+                        Cell::from(format!("{:.2}", t.price)),
+                        Cell::from(format_volume(t.size)),
+                        Cell::from(t.title.clone()),
+                    ]);
+                    if idx == self.selected {
+                        row.style(theme.highlight())
+                    } else {
+                        row
+                    }
+                })
+                .collect();
+
+            let widths = [
+                Constraint::Length(8),
+                Constraint::Length(4),
+                Constraint::Length(8),
+                Constraint::Length(5),
+                Constraint::Length(8),
+                Constraint::Fill(1),
+            ];
+            let header = Row::new(vec!["Time", "Side", "Outcome", "Price", "Size", "Market"])
+                .style(theme.muted());
+
+            frame.render_widget(Table::new(rows, widths).header(header).block(trades_block), chunks[1]);
+        }
+
+        let status = if self.is_loading { " (refreshing...)" } else { "" };
+        let mut help_text = keymap::get().trader.help_line(&[
+            (Action::Back, "Go Back"),
+            (Action::Up, "Up"),
+            (Action::Down, "Down"),
+            (Action::Select, "Open Market"),
+            (Action::Quit, "Quit"),
+        ]);
+        help_text.push_span(Span::styled(status, theme.muted()));
+
+        let help_block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(theme.muted());
+
+        frame.render_widget(Paragraph::new(help_text).block(help_block), chunks[2]);
+    }
+
+    fn handle_input(&mut self, key: KeyEvent, state: &SharedState) -> PageAction {
+        match keymap::get().trader.action(&key) {
+            Some(action) => self.handle_action(action, state),
+            None => PageAction::None,
+        }
+    }
+
+    fn handle_mouse(&mut self, mouse: Mouse, state: &SharedState) -> PageAction {
+        match mouse.kind {
+            MouseKind::ScrollUp => self.handle_action(Action::Up, state),
+            MouseKind::ScrollDown => self.handle_action(Action::Down, state),
+            MouseKind::Click | MouseKind::DoubleClick => {
+                // One header line above the rows.
+                let Some(row) = mouse.row_in(self.trades_area, 1).filter(|row| *row < self.trades.len()) else {
+                    return PageAction::None;
+                };
+                self.selected = row;
+                if mouse.kind == MouseKind::DoubleClick {
+                    self.handle_action(Action::Select, state)
+                } else {
+                    PageAction::None
+                }
+            }
+        }
+    }
+
+    fn handle_action(&mut self, action: Action, _state: &SharedState) -> PageAction {
+        match action {
+            Action::Quit => PageAction::Quit,
+            Action::Back => PageAction::GoBack,
+            Action::Up => {
+                self.selected = self.selected.saturating_sub(1);
+                PageAction::None
+            }
+            Action::Down => {
+                if self.selected < self.trades.len().saturating_sub(1) {
+                    self.selected += 1;
+                }
+                PageAction::None
+            }
+            Action::Select => match self.trades.get(self.selected) {
+                Some(t) => PageAction::NavigateToDetail {
+                    title: format!("Market: {}", t.title),
+                    content: format!("Name: {}\nTraded by: {}", t.title, self.display_name()),
+                    identifier: t.slug.clone(),
+                },
+                None => PageAction::None,
+            },
+            _ => PageAction::None,
+        }
+    }
+}
+
+/// The result of one fetch, applied to the page on its next `refresh`.
+struct Fetched {
+    session_volume: Option<f64>,
+    trades: Result<Vec<Trade>, String>,
+}
+
+/// The page's background task. It stops once the page is dropped and its end of the
+/// request channel with it.
+struct FetchTask {
+    requests: mpsc::UnboundedSender<()>,
+    updates: mpsc::UnboundedReceiver<Fetched>,
+}
+
+impl FetchTask {
+    fn spawn(address: String, events: EventSender) -> Self {
+        let (requests, request_rx) = mpsc::unbounded_channel();
+        let (update_tx, updates) = mpsc::unbounded_channel();
+        tokio::spawn(run_fetches(address, events, request_rx, update_tx));
+        Self { requests, updates }
+    }
+}
+
+async fn run_fetches(
+    address: String,
+    events: EventSender,
+    mut requests: mpsc::UnboundedReceiver<()>,
+    updates: mpsc::UnboundedSender<Fetched>,
+) {
+    while requests.recv().await.is_some() {
+        let session_volume = trader_volume(&events, &address).await;
+        let trades = get_trader_trades(&address).await.map_err(|e| format!("{}", e));
+        if updates.send(Fetched { session_volume, trades }).is_err() {
+            break;
+        }
+    }
+}
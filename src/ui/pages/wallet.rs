@@ -1,8 +1,5 @@
 use std::env;
-use std::time::{Duration, Instant};
-use crossterm::event::{KeyCode, KeyEvent};
-use crossterm::style::Stylize;
-use alloy::{primitives::address, providers::ProviderBuilder, sol};
+use crossterm::event::KeyEvent;
 use crate::actions::approvals::approval_process;
 use crate::actions::wallet_info::get_wallet_full;
 use ratatui::{
@@ -12,6 +9,8 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Wrap},
 };
 use crate::data::state::SharedState;
+use crate::ui::keymap::{self, Action};
+use crate::ui::theme;
 use dotenv::dotenv;
 
 use super::{Page, PageAction};
@@ -25,7 +24,7 @@ pub struct WalletPage {
 impl WalletPage {
     pub fn new(title: String) -> Self {
         Self {
-            title: title,
+            title,
             needs_wallet_update: true,
             pending_approval: false,
             approval_text: "Approval Process: Not running".to_string(),
@@ -46,6 +45,7 @@ impl WalletPage {
                 self.title = format!("Wallet  info fetched\nAddress: {}\nUSDCE Balance: {}\nPOL Balance: {}\n\nUser is approved? {}\n\n{} ", wallet_details.0, wallet_details.1, wallet_details.2, wallet_details.3, self.approval_text).to_string();
             }
             Err(e) => {
+                tracing::warn!("Failed to fetch wallet info: {}", e);
                 self.title = String::from("Error parsing private key");
             }
         }
@@ -101,26 +101,43 @@ impl WalletPage {
 }
 
 impl Page for WalletPage {
-    fn render(&mut self, frame: &mut Frame, area: Rect, state: &SharedState) {
+    fn render(&mut self, frame: &mut Frame, area: Rect, _state: &SharedState) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Percentage(100),
+                Constraint::Min(0),
+                Constraint::Length(1),
             ])
             .split(area);
-        let content_block = Block::default().borders(Borders::ALL);
+        let content_block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(theme::current().border(false));
 
         let contented_paragraph = Paragraph::new(self.title.clone()).wrap(Wrap { trim: true })
             .block(content_block)
             .wrap(Wrap { trim: true });
         frame.render_widget(contented_paragraph, chunks[0]);
+
+        let help_text = keymap::get().wallet.help_line(&[
+            (Action::Back, "Go Back"),
+            (Action::Approve, "Run Approvals"),
+            (Action::Quit, "Quit"),
+        ]);
+        frame.render_widget(Paragraph::new(help_text), chunks[1]);
     }
 
     fn handle_input(&mut self, key: KeyEvent, state: &SharedState) -> PageAction {
-        match key.code {
-            KeyCode::Char('q') => PageAction::Quit,
-            KeyCode::Esc | KeyCode::Backspace => PageAction::GoBack,
-            KeyCode::Char('a') => {
+        match keymap::get().wallet.action(&key) {
+            Some(action) => self.handle_action(action, state),
+            None => PageAction::None,
+        }
+    }
+
+    fn handle_action(&mut self, action: Action, _state: &SharedState) -> PageAction {
+        match action {
+            Action::Quit => PageAction::Quit,
+            Action::Back => PageAction::GoBack,
+            Action::Approve => {
                 self.pending_approval = true;
                 self.title="Approving...".to_string();
                 PageAction::None}
@@ -0,0 +1,262 @@
+use crossterm::event::KeyEvent;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::data::state::SharedState;
+use crate::ui::components::mouse::Mouse;
+use crate::ui::keymap::{self, Action};
+use crate::ui::theme;
+use super::{DetailPage, Page, PageAction};
+
+/// Longest market name shown on a tab.
+const TAB_LABEL_LEN: usize = 24;
+
+/// Open markets as tabs, optionally two side by side. Every tab keeps refreshing
+/// while it is open, whether or not it is on screen.
+pub struct WorkspacePage {
+    pub tabs: Vec<DetailPage>,
+    /// Tab shown in the left and right pane. The right one is only drawn when split.
+    panes: [usize; 2],
+    split: bool,
+    /// Pane receiving keys: 0 left, 1 right.
+    focus: usize,
+    /// Tab bar entries and panes as drawn last frame, for mouse hit-testing.
+    tab_areas: Vec<Rect>,
+    pane_areas: Vec<Rect>,
+}
+
+impl WorkspacePage {
+    pub fn new() -> Self {
+        Self {
+            tabs: vec![],
+            panes: [0, 0],
+            split: false,
+            focus: 0,
+            tab_areas: vec![],
+            pane_areas: vec![],
+        }
+    }
+
+    /// Shows the market in the focused pane, reusing its tab if it is already open.
+    pub fn open(&mut self, title: String, content: String, identifier: String) {
+        let index = match self.tabs.iter().position(|t| t.id == identifier) {
+            Some(index) => index,
+            None => {
+                self.tabs.push(DetailPage::new(title, content, identifier));
+                self.tabs.len() - 1
+            }
+        };
+        self.panes[self.focus] = index;
+    }
+
+    /// Shows the market's tab in the focused pane. Returns false if it has been closed.
+    pub fn show(&mut self, identifier: &str) -> bool {
+        match self.tabs.iter().position(|t| t.id == identifier) {
+            Some(index) => {
+                self.panes[self.focus] = index;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The market in the focused pane.
+    pub fn active_id(&self) -> Option<&str> {
+        self.tabs.get(self.panes[self.focus]).map(|t| t.id.as_str())
+    }
+
+    fn active(&mut self) -> Option<&mut DetailPage> {
+        self.tabs.get_mut(self.panes[self.focus])
+    }
+
+    fn cycle_tab(&mut self, forward: bool) {
+        let len = self.tabs.len();
+        if len == 0 {
+            return;
+        }
+        let current = self.panes[self.focus];
+        self.panes[self.focus] = if forward { (current + 1) % len } else { (current + len - 1) % len };
+    }
+
+    /// Closes the focused tab. Returns false once no tabs are left.
+    fn close_tab(&mut self) -> bool {
+        let closed = self.panes[self.focus];
+        if closed >= self.tabs.len() {
+            return false;
+        }
+        self.tabs.remove(closed);
+        let last = self.tabs.len().saturating_sub(1);
+        for pane in self.panes.iter_mut() {
+            if *pane > closed {
+                *pane -= 1;
+            }
+            *pane = (*pane).min(last);
+        }
+        if self.tabs.len() < 2 {
+            self.split = false;
+            self.focus = 0;
+            self.panes = [0, 0];
+        }
+        !self.tabs.is_empty()
+    }
+
+    /// Splits the view, putting the next tab in the other pane, or goes back to one pane.
+    fn toggle_split(&mut self) {
+        if self.split {
+            self.panes[0] = self.panes[self.focus];
+            self.focus = 0;
+            self.split = false;
+        } else if self.tabs.len() >= 2 {
+            self.panes[1] = (self.panes[0] + 1) % self.tabs.len();
+            self.split = true;
+        }
+    }
+
+    fn tab_label(page: &DetailPage) -> String {
+        let name = page.title.split_once(": ").map(|(_, name)| name).unwrap_or(&page.title);
+        if name.chars().count() > TAB_LABEL_LEN {
+            format!("{}…", name.chars().take(TAB_LABEL_LEN - 1).collect::<String>())
+        } else {
+            name.to_string()
+        }
+    }
+
+    fn render_tab_bar(&mut self, frame: &mut Frame, area: Rect) {
+        let theme = theme::current();
+        let mut spans = Vec::new();
+        let mut x = area.x;
+        self.tab_areas.clear();
+        for (index, page) in self.tabs.iter().enumerate() {
+            let text = format!(" {} ", Self::tab_label(page));
+            let style = if index == self.panes[self.focus] {
+                theme.highlight()
+            } else if self.split && self.panes.contains(&index) {
+                theme.key()
+            } else {
+                theme.muted()
+            };
+            let width = text.chars().count() as u16;
+            self.tab_areas.push(Rect { x, y: area.y, width, height: 1 });
+            spans.push(Span::styled(text, style));
+            spans.push(Span::raw("│"));
+            x += width + 1;
+        }
+        frame.render_widget(Paragraph::new(Line::from(spans)), area);
+    }
+}
+
+impl Page for WorkspacePage {
+    fn render(&mut self, frame: &mut Frame, area: Rect, state: &SharedState) {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .split(area);
+        self.render_tab_bar(frame, rows[0]);
+
+        if self.tabs.is_empty() {
+            self.pane_areas.clear();
+            let help = keymap::get().detail.help_line(&[(Action::Back, "Go Back")]);
+            frame.render_widget(
+                Paragraph::new(vec![Line::raw("No markets open"), help]).block(Block::default().borders(Borders::ALL)),
+                rows[1],
+            );
+            return;
+        }
+
+        let pane_count = if self.split { 2 } else { 1 };
+        self.pane_areas = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![Constraint::Ratio(1, pane_count); pane_count as usize])
+            .split(rows[1])
+            .to_vec();
+
+        for (pane, area) in self.pane_areas.clone().into_iter().enumerate() {
+            if let Some(page) = self.tabs.get_mut(self.panes[pane]) {
+                page.render(frame, area, state);
+            }
+            // The detail layout leaves a margin, which holds the focus frame.
+            if self.split && pane == self.focus {
+                frame.render_widget(
+                    Block::default().borders(Borders::ALL).border_style(theme::current().border(true)),
+                    area,
+                );
+            }
+        }
+    }
+
+    fn handle_input(&mut self, key: KeyEvent, state: &SharedState) -> PageAction {
+        let capturing = self.active().is_some_and(|p| p.is_capturing_input());
+        match keymap::get().detail.action(&key).filter(|_| !capturing) {
+            Some(
+                action @ (Action::NextTab
+                | Action::PrevTab
+                | Action::CloseTab
+                | Action::ToggleSplit
+                | Action::Left
+                | Action::Right),
+            ) => self.handle_action(action, state),
+            _ => match self.active() {
+                Some(page) => page.handle_input(key, state),
+                None => match keymap::get().detail.action(&key) {
+                    Some(Action::Quit) => PageAction::Quit,
+                    Some(Action::Back) => PageAction::GoBack,
+                    _ => PageAction::None,
+                },
+            },
+        }
+    }
+
+    fn handle_action(&mut self, action: Action, state: &SharedState) -> PageAction {
+        match action {
+            Action::NextTab => self.cycle_tab(true),
+            Action::PrevTab => self.cycle_tab(false),
+            Action::CloseTab => {
+                if !self.close_tab() {
+                    return PageAction::GoBack;
+                }
+            }
+            Action::ToggleSplit => self.toggle_split(),
+            Action::Left if self.split => self.focus = 0,
+            Action::Right if self.split => self.focus = 1,
+            _ => {
+                return match self.active() {
+                    Some(page) => page.handle_action(action, state),
+                    None => PageAction::None,
+                };
+            }
+        }
+        PageAction::None
+    }
+
+    fn handle_mouse(&mut self, mouse: Mouse, state: &SharedState) -> PageAction {
+        if mouse.is_click()
+            && let Some(index) = self.tab_areas.iter().position(|area| mouse.is_in(*area))
+        {
+            self.panes[self.focus] = index;
+            return PageAction::None;
+        }
+        // A click in the other pane moves the focus there before it is handled.
+        if let Some(pane) = self.pane_areas.iter().position(|area| mouse.is_in(*area))
+            && pane != self.focus
+            && !self.active().is_some_and(|p| p.is_capturing_input())
+        {
+            self.focus = pane;
+        }
+        match self.active() {
+            Some(page) => page.handle_mouse(mouse, state),
+            None => PageAction::None,
+        }
+    }
+
+    fn is_capturing_input(&self) -> bool {
+        self.tabs.get(self.panes[self.focus]).is_some_and(|p| p.is_capturing_input())
+    }
+
+    fn help_extras(&self) -> &'static [(&'static str, &'static str)] {
+        &[("1-9", "Show or hide an outcome on the chart")]
+    }
+}
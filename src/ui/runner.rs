@@ -1,116 +1,269 @@
 use crossterm::{
-    event::{self, Event},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent},
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
-use ratatui::{Terminal, backend::CrosstermBackend};
+use dotenv::dotenv;
+use ratatui::{Terminal, backend::CrosstermBackend, widgets::Block};
+use std::env;
 use std::io;
+use std::path::Path;
 use std::time::Duration;
 
-use crate::data::state::SharedState;
-use super::app::App;
-use super::pages::{Page, PageAction, PageType};
+use crate::actions::cancel::cancel_all;
+use crate::config;
+use crate::data::pipeline::EventSender;
+use crate::data::state::{SharedState, Snapshot, TimeWindow};
+use crate::export::{export, ExportFormat};
+use super::app::{App, OpenPage};
+use super::components::mouse::Mouse;
+use super::components::palette::{Command, CommandPalette, PaletteEvent};
+use super::components::popup::{render_confirm, render_help, render_notice};
+use super::keymap::{self, Action};
+use super::pages::PageAction;
+use super::theme;
 
-pub async fn run(state: SharedState) -> io::Result<()> {
+pub async fn run(state: SharedState, events: EventSender) -> io::Result<()> {
+    keymap::init();
+    theme::current();
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
     let mut app = App::new();
 
-    loop {
-        if let PageType::Detail = app.current_page {
-            if let Some(ref mut detail) = app.detail_page {
-                if detail.should_refresh() {
-                    detail.fetch_market_data().await;
-                }
+    while !app.should_quit {
+        // Every open market tab keeps refreshing, even when another page is showing.
+        // The fetches run in each tab's own task, so this never waits on the network.
+        for detail in app.workspace.tabs.iter_mut() {
+            detail.refresh(&events);
+        }
 
-                if detail.should_buy_yes() {
-                    if let Some(amount) = detail.get_buy_amount() {
-                        detail.buy(true, amount).await;
-                    }
-                    detail.buy_yes = false;
-                }
-                if detail.should_buy_no() {
-                    if let Some(amount) = detail.get_buy_amount() {
-                        detail.buy(false, amount).await;
-                    }
-                    detail.buy_no = false;
-                }
-            }
+        // Only the dashboard changes its filter and sort, so the view last sent stays
+        // right while another page is showing.
+        if let OpenPage::Dashboard(dashboard) = &app.current {
+            events.set_view(dashboard.view());
         }
 
-        if let PageType::Wallet = app.current_page {
-            if let Some(ref mut wallet) = app.wallet_page {
-                if wallet.needs_wallet_update() {
-                    wallet.fetch_wallet_info().await;
-                }
-                if wallet.needs_approval() {
-                    wallet.run_approval().await;
-                }
+        if let OpenPage::Wallet(wallet) = &mut app.current {
+            if wallet.needs_wallet_update() {
+                wallet.fetch_wallet_info().await;
             }
+            if wallet.needs_approval() {
+                wallet.run_approval().await;
+            }
+        }
+
+        if let OpenPage::Trader(trader) = &mut app.current {
+            trader.refresh(&events);
         }
 
         terminal.draw(|frame| {
             let area = frame.area();
-            match app.current_page {
-                PageType::Dashboard => {
-                    app.dashboard.render(frame, area, &state);
-                }
-                PageType::Detail => {
-                    if let Some(ref mut detail) = app.detail_page {
-                        detail.render(frame, area, &state);
-                    }
-                }
-                PageType::Wallet => {
-                    if let Some(ref mut wallet) = app.wallet_page {
-                        wallet.render(frame, area, &state);
-                    }
-                }
+            frame.render_widget(Block::default().style(theme::current().base()), area);
+            app.page_mut().render(frame, area, &state);
+
+            if app.show_help {
+                let keymaps = keymap::get();
+                let extras = app.page_mut().help_extras();
+                render_help(frame, area, &keymaps.global, keymaps.page(app.current_page()), extras);
+            }
+            if let Some(palette) = app.palette.as_mut() {
+                palette.render(frame, area);
+            }
+            if app.confirm_cancel_all {
+                render_confirm(frame, area, "Cancel all open orders?");
+            }
+            if let Some(notice) = app.notice() {
+                render_notice(frame, area, notice);
             }
         })?;
 
         if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                let action = match app.current_page {
-                    PageType::Dashboard => app.dashboard.handle_input(key, &state),
-                    PageType::Detail => {
-                        if let Some(ref mut detail) = app.detail_page {
-                            detail.handle_input(key, &state)
-                        } else {
-                            PageAction::None
-                        }
-                    }
-                    PageType::Wallet => {
-                        if let Some(ref mut wallet) = app.wallet_page {
-                        wallet.handle_input(key, &state)
-                        } else {
-                            PageAction::None
-                        }
-
-
-
-                    }
-                };
-
-                match action {
-                    PageAction::None => {}
-                    PageAction::Quit => break,
-                    PageAction::GoBack => app.go_back(),
-                    PageAction::NavigateToDetail { title, content, identifier } => {
-                        app.navigate_to_detail(title, content, identifier);
-                    }
-                    PageAction::NavigateToWallet {title } => {
-                        app.navigate_to_wallet(title);
-                    }
+            let action = match event::read()? {
+                Event::Key(key) => handle_key(&mut app, key, &state),
+                Event::Mouse(mouse) => match app.mouse.translate(mouse) {
+                    Some(mouse) => handle_mouse(&mut app, mouse, &state),
+                    None => PageAction::None,
+                },
+                _ => PageAction::None,
+            };
+
+            match action {
+                PageAction::None => {}
+                PageAction::Quit => app.quit(),
+                PageAction::GoBack => app.go_back(),
+                PageAction::GoForward => app.go_forward(),
+                PageAction::NavigateToDetail { title, content, identifier } => {
+                    app.navigate_to_detail(title, content, identifier);
+                }
+                PageAction::NavigateToWallet {title } => {
+                    app.navigate_to_wallet(title);
+                }
+                PageAction::NavigateToTrader { address } => {
+                    app.navigate_to_trader(address);
+                }
+                PageAction::NavigateToLogs => app.navigate_to_logs(),
+                PageAction::CancelAllOrders => {
+                    terminal.draw(|frame| render_notice(frame, frame.area(), "Cancelling all open orders..."))?;
+                    let notice = cancel_all_orders().await;
+                    app.set_notice(notice);
+                }
+                PageAction::Export { window } => {
+                    terminal.draw(|frame| render_notice(frame, frame.area(), "Exporting..."))?;
+                    let snapshot = state.borrow().clone();
+                    let notice = export_lists(&snapshot, window).await;
+                    app.set_notice(notice);
                 }
             }
         }
     }
 
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture)?;
     Ok(())
+}
+
+/// Routes a key to the palette, the cancel-all prompt, the help overlay, the global key
+/// map or the page, in that order.
+fn handle_key(app: &mut App, key: KeyEvent, state: &SharedState) -> PageAction {
+    if let Some(palette) = app.palette.as_mut() {
+        let event = palette.handle_key(key);
+        return palette_event(app, event, state);
+    }
+    if app.confirm_cancel_all {
+        app.confirm_cancel_all = false;
+        return match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => PageAction::CancelAllOrders,
+            _ => PageAction::None,
+        };
+    }
+    if app.show_help {
+        app.show_help = false;
+        return PageAction::None;
+    }
+
+    let capturing = app.page_mut().is_capturing_input();
+    match keymap::get().global.action(&key).filter(|_| !capturing) {
+        Some(action @ (Action::Help | Action::CommandPalette | Action::CycleTheme | Action::Forward | Action::OpenLogs)) => {
+            run_global(app, action)
+        }
+        _ => app.page_mut().handle_input(key, state),
+    }
+}
+
+/// Routes a mouse event like `handle_key`; any click closes the help overlay or declines
+/// the cancel-all prompt.
+fn handle_mouse(app: &mut App, mouse: Mouse, state: &SharedState) -> PageAction {
+    if let Some(palette) = app.palette.as_mut() {
+        let event = palette.handle_mouse(mouse);
+        return palette_event(app, event, state);
+    }
+    if app.confirm_cancel_all {
+        if mouse.is_click() {
+            app.confirm_cancel_all = false;
+        }
+        return PageAction::None;
+    }
+    if app.show_help {
+        if mouse.is_click() {
+            app.show_help = false;
+        }
+        return PageAction::None;
+    }
+    app.page_mut().handle_mouse(mouse, state)
+}
+
+fn palette_event(app: &mut App, event: PaletteEvent, state: &SharedState) -> PageAction {
+    match event {
+        PaletteEvent::None => PageAction::None,
+        PaletteEvent::Close => {
+            app.palette = None;
+            PageAction::None
+        }
+        PaletteEvent::Run(command, argument) => {
+            app.palette = None;
+            run_command(app, command, argument, state)
+        }
+    }
+}
+
+/// Handles an action from the global key map, available on every page.
+fn run_global(app: &mut App, action: Action) -> PageAction {
+    match action {
+        Action::Help => app.show_help = true,
+        Action::CommandPalette => {
+            let keymaps = keymap::get();
+            app.palette = Some(CommandPalette::new(&keymaps.global, keymaps.page(app.current_page())));
+        }
+        Action::CycleTheme => {
+            let theme = theme::cycle();
+            app.set_notice(format!("Theme: {}", theme.name));
+        }
+        Action::Forward => return PageAction::GoForward,
+        Action::OpenLogs => return PageAction::NavigateToLogs,
+        _ => {}
+    }
+    PageAction::None
+}
+
+/// Turns a palette command into the page action it stands for.
+fn run_command(app: &mut App, command: Command, argument: String, state: &SharedState) -> PageAction {
+    match command {
+        Command::Page(action) => app.page_mut().handle_action(action, state),
+        Command::Global(action) => run_global(app, action),
+        Command::OpenMarket => PageAction::NavigateToDetail {
+            title: format!("Market: {}", argument),
+            content: format!("Slug: {}", argument),
+            identifier: argument,
+        },
+        Command::Wallet => PageAction::NavigateToWallet { title: String::from("Wallet") },
+        Command::CancelAll => {
+            app.confirm_cancel_all = true;
+            PageAction::None
+        }
+        Command::Quit => PageAction::Quit,
+    }
+}
+
+async fn cancel_all_orders() -> String {
+    dotenv().ok();
+    let Ok(private_key) = env::var("PRIVATE_KEY") else {
+        return String::from("PRIVATE_KEY is not set");
+    };
+    match cancel_all(&private_key).await {
+        Ok(response) if response.not_canceled.is_empty() => {
+            format!("Cancelled {} orders", response.canceled.len())
+        }
+        Ok(response) => format!(
+            "Cancelled {} orders, {} could not be cancelled",
+            response.canceled.len(),
+            response.not_canceled.len()
+        ),
+        Err(e) => {
+            tracing::error!("Cancel all orders failed: {}", e);
+            format!("Cancel failed: {}", e)
+        }
+    }
+}
+
+async fn export_lists(snapshot: &Snapshot, window: TimeWindow) -> String {
+    let config = config::get();
+    let Some(format) = ExportFormat::from_name(&config.export_format) else {
+        return format!("Unknown export_format '{}'", config.export_format);
+    };
+    match export(snapshot, window, Path::new(&config.export_dir), format).await {
+        Ok(exported) => {
+            for error in &exported.errors {
+                tracing::warn!("Export incomplete: {}", error);
+            }
+            exported.summary()
+        }
+        Err(e) => {
+            tracing::error!("Export failed: {}", e);
+            format!("Export failed: {}", e)
+        }
+    }
 }
\ No newline at end of file
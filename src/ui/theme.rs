@@ -0,0 +1,167 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use ratatui::style::{Color, Modifier, Style};
+
+use crate::config;
+
+/// Colours for every role the UI draws with.
+pub struct Theme {
+    pub name: &'static str,
+    /// Fills the whole screen; `Reset` keeps the terminal's own colours.
+    pub background: Color,
+    pub text: Color,
+    /// Secondary text such as table headers and status hints.
+    pub muted: Color,
+    pub border: Color,
+    /// Border of the focused panel or popup, and key hints.
+    pub focus: Color,
+    /// Border of page title blocks.
+    pub title: Color,
+    pub highlight_fg: Color,
+    pub highlight_bg: Color,
+    /// Sparklines and notices.
+    pub accent: Color,
+    /// Buys and rising prices.
+    pub up: Color,
+    /// Sells and falling prices.
+    pub down: Color,
+    pub error: Color,
+    pub popup_bg: Color,
+    /// Line colours for chart series, used in order.
+    pub series: [Color; 4],
+}
+
+impl Theme {
+    pub fn base(&self) -> Style {
+        Style::default().fg(self.text).bg(self.background)
+    }
+
+    pub fn muted(&self) -> Style {
+        Style::default().fg(self.muted)
+    }
+
+    pub fn border(&self, focused: bool) -> Style {
+        Style::default().fg(if focused { self.focus } else { self.border })
+    }
+
+    pub fn title(&self) -> Style {
+        Style::default().fg(self.title)
+    }
+
+    pub fn key(&self) -> Style {
+        Style::default().fg(self.focus).add_modifier(Modifier::BOLD)
+    }
+
+    pub fn highlight(&self) -> Style {
+        Style::default()
+            .fg(self.highlight_fg)
+            .bg(self.highlight_bg)
+            .add_modifier(Modifier::BOLD)
+    }
+
+    /// Colour for a buy/sell side or the sign of a price change.
+    pub fn direction(&self, positive: bool) -> Color {
+        if positive { self.up } else { self.down }
+    }
+}
+
+pub const THEMES: [Theme; 4] = [
+    Theme {
+        name: "dark",
+        background: Color::Reset,
+        text: Color::White,
+        muted: Color::DarkGray,
+        border: Color::White,
+        focus: Color::Yellow,
+        title: Color::Cyan,
+        highlight_fg: Color::Reset,
+        highlight_bg: Color::Blue,
+        accent: Color::Cyan,
+        up: Color::Green,
+        down: Color::Red,
+        error: Color::Red,
+        popup_bg: Color::Black,
+        series: [Color::Green, Color::Red, Color::Cyan, Color::Magenta],
+    },
+    Theme {
+        name: "light",
+        background: Color::White,
+        text: Color::Black,
+        muted: Color::Gray,
+        border: Color::Black,
+        focus: Color::Blue,
+        title: Color::Blue,
+        highlight_fg: Color::Black,
+        highlight_bg: Color::LightBlue,
+        accent: Color::Magenta,
+        up: Color::Green,
+        down: Color::Red,
+        error: Color::Red,
+        popup_bg: Color::White,
+        series: [Color::Green, Color::Red, Color::Blue, Color::Magenta],
+    },
+    Theme {
+        name: "high-contrast",
+        background: Color::Black,
+        text: Color::White,
+        muted: Color::Gray,
+        border: Color::White,
+        focus: Color::LightYellow,
+        title: Color::White,
+        highlight_fg: Color::Black,
+        highlight_bg: Color::White,
+        accent: Color::LightCyan,
+        up: Color::LightGreen,
+        down: Color::LightRed,
+        error: Color::LightRed,
+        popup_bg: Color::Black,
+        series: [Color::LightGreen, Color::LightRed, Color::LightCyan, Color::LightYellow],
+    },
+    // Blue/orange instead of green/red so buys and sells stay distinguishable with
+    // red-green colour blindness (Okabe-Ito palette).
+    Theme {
+        name: "colorblind",
+        background: Color::Reset,
+        text: Color::White,
+        muted: Color::DarkGray,
+        border: Color::White,
+        focus: Color::Yellow,
+        title: Color::Cyan,
+        highlight_fg: Color::Reset,
+        highlight_bg: Color::DarkGray,
+        accent: Color::Cyan,
+        up: Color::Rgb(86, 180, 233),
+        down: Color::Rgb(230, 159, 0),
+        error: Color::Rgb(213, 94, 0),
+        popup_bg: Color::Black,
+        series: [
+            Color::Rgb(86, 180, 233),
+            Color::Rgb(230, 159, 0),
+            Color::Rgb(0, 158, 115),
+            Color::Rgb(204, 121, 167),
+        ],
+    },
+];
+
+static CURRENT: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+/// The active theme, initially the one named by `theme` in the config.
+pub fn current() -> &'static Theme {
+    let mut index = CURRENT.load(Ordering::Relaxed);
+    if index == usize::MAX {
+        let name = &config::get().theme;
+        index = THEMES.iter().position(|t| t.name == name).unwrap_or_else(|| {
+            tracing::warn!("Unknown theme '{}', using dark", name);
+            0
+        });
+        CURRENT.store(index, Ordering::Relaxed);
+    }
+    &THEMES[index]
+}
+
+/// Switches to the next theme, returning it.
+pub fn cycle() -> &'static Theme {
+    let next = (THEMES.iter().position(|t| std::ptr::eq(t, current())).unwrap_or(0) + 1) % THEMES.len();
+    CURRENT.store(next, Ordering::Relaxed);
+    &THEMES[next]
+}